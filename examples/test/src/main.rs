@@ -4,7 +4,6 @@ mod dialog;
 mod event;
 mod notification;
 mod os;
-mod tauri_log;
 mod window;
 mod global_shortcut;
 
@@ -14,7 +13,7 @@ use std::future::Future;
 use std::panic;
 use sycamore::prelude::*;
 use sycamore::suspense::Suspense;
-use tauri_log::TauriLogger;
+use tauri_sys::plugin_log::TauriLogger;
 
 #[cfg(feature = "ci")]
 async fn exit_with_error(e: String) {
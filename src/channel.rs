@@ -0,0 +1,181 @@
+//! The queue backing event streams.
+
+use futures::Stream;
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+/// How a bounded stream handles events that arrive while its buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Discard the oldest buffered event to make room for the new one.
+    DropOldest,
+    /// Discard the new event, keeping the buffered ones.
+    DropNewest,
+    /// Replace the most recently buffered event with the new one.
+    Coalesce,
+}
+
+/// Configures how many events a stream buffers until they are read.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::event::{listen_with, Overflow, StreamConfig};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // keep at most 16 progress events, discarding the oldest ones
+/// let progress = listen_with::<u32>("progress", StreamConfig::bounded(16, Overflow::DropOldest)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamConfig {
+    /// The maximum number of buffered events, `None` means unbounded.
+    ///
+    /// A capacity of `0` is treated as `1`.
+    pub capacity: Option<usize>,
+    /// What to do with events arriving while the buffer is full.
+    pub overflow: Overflow,
+}
+
+impl StreamConfig {
+    /// Buffers all events until they are read. This is the default.
+    pub const fn unbounded() -> Self {
+        Self {
+            capacity: None,
+            overflow: Overflow::DropOldest,
+        }
+    }
+
+    /// Buffers at most `capacity` events, applying `overflow` once full.
+    pub const fn bounded(capacity: usize, overflow: Overflow) -> Self {
+        Self {
+            capacity: Some(capacity),
+            overflow,
+        }
+    }
+
+    /// Only keeps the most recent event.
+    pub const fn latest() -> Self {
+        Self::bounded(1, Overflow::Coalesce)
+    }
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self::unbounded()
+    }
+}
+
+struct Shared<T> {
+    queue: VecDeque<T>,
+    config: StreamConfig,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+pub(crate) fn channel<T>(config: StreamConfig) -> (Sender<T>, Receiver<T>) {
+    let shared = Rc::new(RefCell::new(Shared {
+        queue: VecDeque::new(),
+        config,
+        waker: None,
+        closed: false,
+    }));
+
+    (
+        Sender {
+            shared: shared.clone(),
+        },
+        Receiver { shared },
+    )
+}
+
+pub(crate) struct Sender<T> {
+    shared: Rc<RefCell<Shared<T>>>,
+}
+
+impl<T> Sender<T> {
+    /// Whether the receiving end was dropped.
+    pub fn is_closed(&self) -> bool {
+        self.shared.borrow().closed
+    }
+
+    /// Queues an item, applying the overflow policy if the queue is full.
+    pub fn send(&self, item: T) {
+        let mut shared = self.shared.borrow_mut();
+
+        if shared.closed {
+            return;
+        }
+
+        match shared.config.capacity.map(|capacity| capacity.max(1)) {
+            Some(capacity) if shared.queue.len() >= capacity => match shared.config.overflow {
+                Overflow::DropOldest => {
+                    shared.queue.pop_front();
+                    shared.queue.push_back(item);
+                }
+                Overflow::DropNewest => {}
+                Overflow::Coalesce => {
+                    shared.queue.pop_back();
+                    shared.queue.push_back(item);
+                }
+            },
+            _ => shared.queue.push_back(item),
+        }
+
+        let waker = shared.waker.take();
+        drop(shared);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut shared = self.shared.borrow_mut();
+            shared.closed = true;
+            shared.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+pub(crate) struct Receiver<T> {
+    shared: Rc<RefCell<Shared<T>>>,
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.borrow_mut();
+        shared.closed = true;
+        shared.queue.clear();
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut shared = self.shared.borrow_mut();
+
+        if let Some(item) = shared.queue.pop_front() {
+            Poll::Ready(Some(item))
+        } else if shared.closed {
+            Poll::Ready(None)
+        } else {
+            shared.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
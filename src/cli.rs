@@ -0,0 +1,133 @@
+//! Parse arguments from your Command Line Interface.
+//!
+//! The CLI definition must be added to `tauri.cli` in `tauri.conf.json`:
+//! ```json
+//! {
+//!     "tauri": {
+//!         "cli": {
+//!             "args": [
+//!                 {
+//!                     "name": "verbose",
+//!                     "short": "v"
+//!                 },
+//!                 {
+//!                     "name": "config",
+//!                     "takesValue": true
+//!                 }
+//!             ]
+//!         }
+//!     }
+//! }
+//! ```
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The value of a matched argument.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ArgValue {
+    /// A flag that doesn't take a value.
+    Bool(bool),
+    /// A single value.
+    String(String),
+    /// An argument that takes multiple values.
+    Array(Vec<String>),
+    /// An argument that takes a value, but wasn't given one.
+    Null,
+}
+
+/// A matched argument.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ArgMatch {
+    /// The value of the argument.
+    pub value: ArgValue,
+    /// The number of times the argument was passed.
+    pub occurrences: u32,
+}
+
+/// A matched subcommand.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SubcommandMatch {
+    /// The name of the subcommand.
+    pub name: String,
+    /// The arguments matched by the subcommand.
+    pub matches: CliMatches,
+}
+
+/// The result of matching the process arguments against the CLI definition.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CliMatches {
+    /// The matched arguments, keyed by name.
+    pub args: HashMap<String, ArgMatch>,
+    /// The matched subcommand, if any.
+    pub subcommand: Option<Box<SubcommandMatch>>,
+}
+
+impl CliMatches {
+    /// Deserializes the argument values into a user-defined type.
+    ///
+    /// Each argument is mapped to a field of the same name, the subcommand is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use serde::Deserialize;
+    /// use tauri_sys::cli::get_matches;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Args {
+    ///     verbose: bool,
+    ///     config: Option<String>,
+    /// }
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let args: Args = get_matches().await?.parse_into()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parse_into<T: DeserializeOwned>(&self) -> crate::Result<T> {
+        let values: HashMap<&str, &ArgValue> = self
+            .args
+            .iter()
+            .map(|(name, arg)| (name.as_str(), &arg.value))
+            .collect();
+
+        let raw = values.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?;
+
+        Ok(serde_wasm_bindgen::from_value(raw)?)
+    }
+}
+
+/// Parse the arguments provided to the current process and get the matches using the configuration defined [`tauri.cli`](https://tauri.app/v1/api/config/#tauriconfig.cli) in `tauri.conf.json`
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::cli::get_matches;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let matches = get_matches().await?;
+///
+/// if let Some(subcommand) = matches.subcommand {
+///     log::info!("Running subcommand {}", subcommand.name);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[inline(always)]
+pub async fn get_matches() -> crate::Result<CliMatches> {
+    let raw = inner::getMatches().await?;
+
+    Ok(serde_wasm_bindgen::from_value(raw)?)
+}
+
+mod inner {
+    use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+    #[wasm_bindgen(module = "/src/cli.js")]
+    extern "C" {
+        #[wasm_bindgen(catch)]
+        pub async fn getMatches() -> Result<JsValue, JsValue>;
+    }
+}
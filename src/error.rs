@@ -1,3 +1,4 @@
+use serde::de::DeserializeOwned;
 use std::path::PathBuf;
 use wasm_bindgen::JsValue;
 
@@ -5,14 +6,98 @@ use wasm_bindgen::JsValue;
 pub enum Error {
     #[error("Command returned Error: {0}")]
     Command(String),
+    /// A structured error returned by the backend, serialized as JSON. Use [`Error::decode`] to recover it.
+    #[error("Command returned Error: {0}")]
+    Backend(String),
+    /// The call was rejected by the allowlist or the configured scope.
+    #[error("Permission denied: {0}")]
+    Permission(String),
     #[error("Failed to parse JSON: {0}")]
     Serde(String),
     #[cfg(any(feature = "event", feature = "window"))]
     #[error("Oneshot cancelled: {0}")]
     OneshotCanceled(#[from] futures::channel::oneshot::Canceled),
-    #[cfg(feature = "fs")]
+    #[cfg(feature = "event")]
+    #[error("Invalid event payload: {message}")]
+    InvalidPayload {
+        /// Why the payload couldn't be deserialized.
+        message: String,
+        /// The raw payload as received from the backend, serialized as JSON.
+        ///
+        /// Use [`Error::raw`] to get it back as a `JsValue`.
+        raw: String,
+    },
+    #[cfg(feature = "event")]
+    #[error("Request timed out after {0:?}")]
+    Timeout(std::time::Duration),
+    #[cfg(feature = "event")]
+    #[error("Request handler returned Error: {0}")]
+    Rpc(String),
+    /// There is no window with the given label.
+    #[cfg(feature = "window")]
+    #[error("No window with label: {0}")]
+    UnknownWindow(String),
+    #[cfg(feature = "updater")]
+    #[error("Updater returned Error: {0}")]
+    Updater(String),
+    #[cfg(any(feature = "fs", feature = "shell"))]
     #[error("Could not convert path to string")]
     Utf8(PathBuf),
+    #[cfg(feature = "shell")]
+    #[error("Invalid sidecar program name: {0}")]
+    InvalidSidecar(String),
+}
+
+impl Error {
+    /// Deserializes the error returned by the backend into `E`.
+    ///
+    /// Returns `None` if the error didn't originate from the backend or doesn't match `E`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use serde::Deserialize;
+    /// use tauri_sys::tauri::invoke;
+    ///
+    /// #[derive(Deserialize)]
+    /// enum LoginError {
+    ///     UnknownUser,
+    ///     WrongPassword,
+    /// }
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// if let Err(err) = invoke::<_, ()>("login", &()).await {
+    ///     match err.decode::<LoginError>() {
+    ///         Some(LoginError::UnknownUser) => log::warn!("Unknown user"),
+    ///         Some(LoginError::WrongPassword) => log::warn!("Wrong password"),
+    ///         None => return Err(err.into()),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn decode<E: DeserializeOwned>(&self) -> Option<E> {
+        match self {
+            Error::Backend(_) => serde_wasm_bindgen::from_value(self.raw()?).ok(),
+            Error::Command(msg) => serde_wasm_bindgen::from_value(JsValue::from_str(msg)).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw value a [`Error::Backend`] or [`Error::InvalidPayload`] error was created from.
+    ///
+    /// Errors only keep the JSON representation of the value, so `Error` stays `Send` and `Sync`.
+    /// Values that can't be represented as JSON, like functions or `undefined`, are not recovered.
+    pub fn raw(&self) -> Option<JsValue> {
+        let json = match self {
+            Error::Backend(json) => json,
+            #[cfg(feature = "event")]
+            Error::InvalidPayload { raw, .. } => raw,
+            _ => return None,
+        };
+
+        js_sys::JSON::parse(json).ok()
+    }
 }
 
 impl From<serde_wasm_bindgen::Error> for Error {
@@ -23,6 +108,25 @@ impl From<serde_wasm_bindgen::Error> for Error {
 
 impl From<JsValue> for Error {
     fn from(e: JsValue) -> Self {
-        Self::Command(format!("{:?}", e))
+        match e.as_string() {
+            Some(msg) if is_permission_error(&msg) => Self::Permission(msg),
+            Some(msg) => Self::Command(msg),
+            None => Self::Backend(to_json(&e)),
+        }
     }
 }
+
+/// Whether a rejection was caused by the allowlist or a scope, as opposed to the command itself.
+fn is_permission_error(msg: &str) -> bool {
+    msg.contains("not in the allowlist")
+        || msg.contains("not allowed on the configured scope")
+        || msg.contains("module is not enabled")
+}
+
+/// Serializes a raw value to JSON, falling back to its debug representation.
+pub(crate) fn to_json(raw: &JsValue) -> String {
+    js_sys::JSON::stringify(raw)
+        .ok()
+        .and_then(|json| json.as_string())
+        .unwrap_or_else(|| format!("{:?}", raw))
+}
@@ -1,12 +1,17 @@
 //! The event system allows you to emit events to the backend and listen to events from it.
 
-use futures::{
-    channel::{mpsc, oneshot},
-    Future, FutureExt, Stream, StreamExt,
-};
+use crate::channel;
+pub use crate::channel::{Overflow, StreamConfig};
+use futures::{channel::oneshot, future, Future, FutureExt, Stream, StreamExt};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::fmt::Debug;
 use wasm_bindgen::{prelude::Closure, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+mod hub;
+pub mod rpc;
+
+pub use hub::Hub;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -21,6 +26,58 @@ pub struct Event<T> {
     pub window_label: Option<String>,
 }
 
+/// A statically known event, tying the event name to the type of its payload.
+///
+/// Use the [`tauri_event!`](crate::tauri_event) macro to define events, and [`listen_typed`], [`once_typed`] and [`emit_typed`] to use them.
+/// Renaming an event or changing its payload then becomes a compile error at every call site.
+pub trait TauriEvent {
+    /// Event name. Must include only alphanumeric characters, `-`, `/`, `:` and `_`.
+    const NAME: &'static str;
+    /// The payload carried by the event.
+    type Payload: Serialize + DeserializeOwned + 'static;
+}
+
+/// Defines one or more [`TauriEvent`]s as unit structs.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::{Deserialize, Serialize};
+/// use tauri_sys::{event::emit_typed, tauri_event};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct LoadedPayload {
+///     logged_in: bool,
+///     token: String,
+/// }
+///
+/// tauri_event! {
+///     /// Emitted once the frontend finished loading.
+///     pub struct Loaded("frontend-loaded"): LoadedPayload;
+///     pub struct Quit("quit"): ();
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// emit_typed::<Loaded>(&LoadedPayload { logged_in: true, token: "authToken".to_string() }).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! tauri_event {
+    ($($(#[$meta:meta])* $vis:vis struct $name:ident($event:literal): $payload:ty;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy)]
+            $vis struct $name;
+
+            impl $crate::event::TauriEvent for $name {
+                const NAME: &'static str = $event;
+                type Payload = $payload;
+            }
+        )*
+    };
+}
+
 /// Emits an event to the backend.
 ///
 /// # Example
@@ -46,8 +103,83 @@ pub async fn emit<T: Serialize>(event: &str, payload: &T) -> crate::Result<()> {
     Ok(())
 }
 
+/// Emits an event to the windows with the given labels.
+///
+/// Returns the result of the emit for every window the event was sent to, paired with the window's label.
+/// Labels that don't belong to any window follow with an [`Error::UnknownWindow`](crate::Error::UnknownWindow).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::event::emit_to;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let results = emit_to(["editor-1", "editor-2"], "document-saved", &"notes.md").await;
+///
+/// for (label, res) in results {
+///     if let Err(err) = res {
+///         log::error!("Failed to notify {}: {}", label, err);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "window")]
+pub async fn emit_to<'a, T: Serialize>(
+    labels: impl IntoIterator<Item = &'a str>,
+    event: &str,
+    payload: &T,
+) -> Vec<(String, crate::Result<()>)> {
+    let labels: Vec<&str> = labels.into_iter().collect();
+    let mut results = emit_filtered(|label| labels.contains(&label), event, payload).await;
+
+    for label in labels {
+        if !results.iter().any(|(emitted, _)| emitted == label) {
+            let err = crate::Error::UnknownWindow(label.to_string());
+            results.push((label.to_string(), Err(err)));
+        }
+    }
+
+    results
+}
+
+/// Emits an event to all windows whose label matches the predicate.
+///
+/// Returns the result of the emit for every window the event was sent to, paired with the window's label.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::event::emit_filtered;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let results = emit_filtered(|label| label.starts_with("editor-"), "theme-changed", &"dark").await;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "window")]
+pub async fn emit_filtered<T: Serialize>(
+    mut predicate: impl FnMut(&str) -> bool,
+    event: &str,
+    payload: &T,
+) -> Vec<(String, crate::Result<()>)> {
+    let emits = crate::window::all_windows()
+        .into_iter()
+        .map(|win| (win.label(), win))
+        .filter(|(label, _)| predicate(label))
+        .map(|(label, win)| async move {
+            let res = win.emit(event, payload).await;
+
+            (label, res)
+        });
+
+    future::join_all(emits).await
+}
+
 /// Listen to an event from the backend.
-/// 
+///
+/// Events whose payload can't be deserialized into `T` are logged and skipped, use [`listen_fallible`] to handle them yourself.
+///
 /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
 /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
 ///
@@ -68,29 +200,174 @@ pub async fn listen<T>(event: &str) -> crate::Result<impl Stream<Item = Event<T>
 where
     T: DeserializeOwned + 'static,
 {
-    let (tx, rx) = mpsc::unbounded::<Event<T>>();
+    listen_with(event, StreamConfig::default()).await
+}
+
+/// Listen to an event from the backend, buffering events as configured.
+///
+/// See [`listen`] for details.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::event::{listen_with, StreamConfig};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // a slow consumer only ever sees the latest progress
+/// let mut progress = listen_with::<u32>("download-progress", StreamConfig::latest()).await?;
+///
+/// while let Some(event) = progress.next().await {
+///     log::info!("Downloaded {}%", event.payload);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[inline(always)]
+pub async fn listen_with<T>(
+    event: &str,
+    config: StreamConfig,
+) -> crate::Result<impl Stream<Item = Event<T>>>
+where
+    T: DeserializeOwned + 'static,
+{
+    let events = listen_raw(event, config).await?;
+
+    Ok(skip_invalid(events))
+}
+
+/// Listen to an event from the backend, yielding an error for every event whose payload can't be deserialized into `T`.
+///
+/// The error is an [`Error::InvalidPayload`](crate::Error::InvalidPayload) that carries the raw payload, so it can be logged or inspected.
+///
+/// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+/// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::{event::listen_fallible, Error};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut events = listen_fallible::<String>("error").await?;
+///
+/// while let Some(event) = events.next().await {
+///     match event {
+///         Ok(event) => log::info!("Got error: {}", event.payload),
+///         Err(Error::InvalidPayload { message, raw }) => {
+///             log::warn!("Malformed error event {}: {}", raw, message)
+///         }
+///         Err(err) => return Err(err.into()),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[inline(always)]
+pub async fn listen_fallible<T>(
+    event: &str,
+) -> crate::Result<impl Stream<Item = crate::Result<Event<T>>>>
+where
+    T: DeserializeOwned + 'static,
+{
+    listen_raw(event, StreamConfig::default()).await
+}
+
+async fn listen_raw<T>(
+    event: &str,
+    config: StreamConfig,
+) -> crate::Result<Listen<crate::Result<Event<T>>>>
+where
+    T: DeserializeOwned + 'static,
+{
+    let (tx, rx) = channel::channel(config);
 
     let closure = Closure::<dyn FnMut(JsValue)>::new(move |raw| {
-        let _ = tx.unbounded_send(serde_wasm_bindgen::from_value(raw).unwrap());
+        tx.send(deserialize_event(raw));
     });
     let unlisten = inner::listen(event, &closure).await?;
-    closure.forget();
 
     Ok(Listen {
         rx,
         unlisten: js_sys::Function::from(unlisten),
+        handler: Some(closure),
+    })
+}
+
+/// The label of the current window, as injected by Tauri into `window.__TAURI_METADATA__`.
+///
+/// Returns `None` when running outside of a Tauri window, unless the windows were mocked.
+pub(crate) fn current_window_label() -> Option<String> {
+    ["__TAURI_METADATA__", "__currentWindow", "label"]
+        .iter()
+        .try_fold(JsValue::from(js_sys::global()), |value, key| {
+            js_sys::Reflect::get(&value, &JsValue::from_str(key)).ok()
+        })
+        .and_then(|label| label.as_string())
+}
+
+/// Deserializes a raw event, keeping the raw value around if that fails.
+pub(crate) fn deserialize_event<T: DeserializeOwned>(raw: JsValue) -> crate::Result<Event<T>> {
+    serde_wasm_bindgen::from_value(raw.clone()).map_err(|err| crate::Error::InvalidPayload {
+        message: err.to_string(),
+        raw: crate::error::to_json(&raw),
+    })
+}
+
+/// Logs and drops events that failed to deserialize.
+pub(crate) fn skip_invalid<T>(
+    events: impl Stream<Item = crate::Result<Event<T>>>,
+) -> impl Stream<Item = Event<T>> {
+    events.filter_map(|event| {
+        future::ready(match event {
+            Ok(event) => Some(event),
+            Err(err) => {
+                log::error!("Skipping event: {}", err);
+                None
+            }
+        })
     })
 }
 
+/// Calls `unlisten` and drops the handler once the listener is detached.
+///
+/// The handler has to outlive the listener, as the backend might still call it until then.
+pub(crate) fn unlisten(unlisten: &js_sys::Function, handler: Option<Closure<dyn FnMut(JsValue)>>) {
+    let detached = match unlisten.call0(&JsValue::NULL) {
+        Ok(res) => JsFuture::from(js_sys::Promise::resolve(&res)),
+        Err(err) => {
+            log::error!("Failed to unlisten: {:?}", err);
+            if let Some(handler) = handler {
+                handler.forget();
+            }
+            return;
+        }
+    };
+
+    wasm_bindgen_futures::spawn_local(async move {
+        match detached.await {
+            Ok(_) => drop(handler),
+            Err(err) => {
+                log::error!("Failed to unlisten: {:?}", err);
+                if let Some(handler) = handler {
+                    handler.forget();
+                }
+            }
+        }
+    });
+}
+
 pub(crate) struct Listen<T> {
-    pub rx: mpsc::UnboundedReceiver<T>,
+    pub rx: channel::Receiver<T>,
     pub unlisten: js_sys::Function,
+    pub handler: Option<Closure<dyn FnMut(JsValue)>>,
 }
 
 impl<T> Drop for Listen<T> {
     fn drop(&mut self) {
         log::debug!("Calling unlisten for listen callback");
-        self.unlisten.call0(&wasm_bindgen::JsValue::NULL).unwrap();
+        unlisten(&self.unlisten, self.handler.take());
     }
 }
 
@@ -107,6 +384,8 @@ impl<T> Stream for Listen<T> {
 
 /// Listen to an one-off event from the backend.
 ///
+/// Fails with [`Error::InvalidPayload`](crate::Error::InvalidPayload) if the payload can't be deserialized into `T`.
+///
 /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
 /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
 /// 
@@ -135,32 +414,55 @@ pub async fn once<T>(event: &str) -> crate::Result<Event<T>>
 where
     T: DeserializeOwned + 'static,
 {
-    let (tx, rx) = oneshot::channel::<Event<T>>();
+    let (tx, rx) = oneshot::channel::<crate::Result<Event<T>>>();
 
     let closure: Closure<dyn FnMut(JsValue)> = Closure::once(move |raw| {
-        let _ = tx.send(serde_wasm_bindgen::from_value(raw).unwrap());
+        let _ = tx.send(deserialize_event(raw));
     });
     let unlisten = inner::once(event, &closure).await?;
-    closure.forget();
 
     let fut = Once {
         rx,
         unlisten: js_sys::Function::from(unlisten),
+        handler: Some(closure),
     };
 
     fut.await
 }
 
+/// Emits a [`TauriEvent`] to the backend.
+#[inline(always)]
+pub async fn emit_typed<E: TauriEvent>(payload: &E::Payload) -> crate::Result<()> {
+    emit(E::NAME, payload).await
+}
+
+/// Listen to a [`TauriEvent`] from the backend.
+///
+/// See [`listen`] for details.
+#[inline(always)]
+pub async fn listen_typed<E: TauriEvent>() -> crate::Result<impl Stream<Item = Event<E::Payload>>> {
+    listen(E::NAME).await
+}
+
+/// Listen to an one-off [`TauriEvent`] from the backend.
+///
+/// See [`once`] for details.
+#[inline(always)]
+pub async fn once_typed<E: TauriEvent>() -> crate::Result<Event<E::Payload>> {
+    once(E::NAME).await
+}
+
 pub(crate) struct Once<T> {
-    pub rx: oneshot::Receiver<Event<T>>,
+    pub rx: oneshot::Receiver<crate::Result<Event<T>>>,
     pub unlisten: js_sys::Function,
+    pub handler: Option<Closure<dyn FnMut(JsValue)>>,
 }
 
 impl<T> Drop for Once<T> {
     fn drop(&mut self) {
         self.rx.close();
         log::debug!("Calling unlisten for once callback");
-        self.unlisten.call0(&wasm_bindgen::JsValue::NULL).unwrap();
+        unlisten(&self.unlisten, self.handler.take());
     }
 }
 
@@ -171,7 +473,9 @@ impl<T> Future for Once<T> {
         mut self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Self::Output> {
-        self.rx.poll_unpin(cx).map_err(Into::into)
+        self.rx
+            .poll_unpin(cx)
+            .map(|res| res.map_err(Into::into).and_then(|event| event))
     }
 }
 
@@ -0,0 +1,180 @@
+use super::{deserialize_event, inner, skip_invalid, unlisten, Event, StreamConfig};
+use crate::channel;
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    marker::PhantomData,
+    pin::Pin,
+    rc::{Rc, Weak},
+    task::{Context, Poll},
+};
+use wasm_bindgen::{prelude::Closure, JsValue};
+
+type Topics = RefCell<HashMap<String, Weak<Topic>>>;
+type Subscribers = Rc<RefCell<Vec<channel::Sender<JsValue>>>>;
+
+/// Shares one underlying event listener between any number of subscribers.
+///
+/// Every call to [`listen`](super::listen) registers a separate listener with the backend.
+/// A hub instead registers a single listener per event name on the first [`Hub::subscribe`] and fans the events out to all subscribers.
+/// The listener is detached once the last subscriber is dropped.
+///
+/// Hubs are cheap to clone, clones share their listeners.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::event::Hub;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let hub = Hub::new();
+///
+/// // both streams are backed by the same listener
+/// let mut a = hub.subscribe::<String>("status").await?;
+/// let mut b = hub.clone().subscribe::<String>("status").await?;
+///
+/// let (a, b) = futures::join!(a.next(), b.next());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Hub {
+    topics: Rc<Topics>,
+}
+
+impl Hub {
+    /// Creates a hub without any subscriptions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe to an event from the backend.
+    ///
+    /// Events whose payload can't be deserialized into `T` are logged and skipped.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when the last subscriber is dropped, so no manual unlisten function needs to be called.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    pub async fn subscribe<T>(&self, event: &str) -> crate::Result<impl Stream<Item = Event<T>>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        self.subscribe_with(event, StreamConfig::default()).await
+    }
+
+    /// Subscribe to an event from the backend, buffering events as configured.
+    ///
+    /// See [`Hub::subscribe`] for details.
+    pub async fn subscribe_with<T>(
+        &self,
+        event: &str,
+        config: StreamConfig,
+    ) -> crate::Result<impl Stream<Item = Event<T>>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let topic = match self.topic(event) {
+            Some(topic) => topic,
+            None => {
+                let topic = Topic::listen(event, Rc::downgrade(&self.topics)).await?;
+
+                // another subscriber might have registered the topic while we were waiting
+                match self.topic(event) {
+                    Some(existing) => existing,
+                    None => {
+                        self.topics
+                            .borrow_mut()
+                            .insert(event.to_string(), Rc::downgrade(&topic));
+                        topic
+                    }
+                }
+            }
+        };
+
+        let (tx, rx) = channel::channel(config);
+        topic.subscribers.borrow_mut().push(tx);
+
+        Ok(skip_invalid(Subscription {
+            rx,
+            _topic: topic,
+            _payload: PhantomData::<fn() -> T>,
+        }))
+    }
+
+    fn topic(&self, event: &str) -> Option<Rc<Topic>> {
+        self.topics.borrow().get(event).and_then(Weak::upgrade)
+    }
+}
+
+struct Topic {
+    event: String,
+    topics: Weak<Topics>,
+    subscribers: Subscribers,
+    unlisten: js_sys::Function,
+    handler: Option<Closure<dyn FnMut(JsValue)>>,
+}
+
+impl Topic {
+    async fn listen(event: &str, topics: Weak<Topics>) -> crate::Result<Rc<Self>> {
+        let subscribers: Subscribers = Default::default();
+
+        let closure = Closure::<dyn FnMut(JsValue)>::new({
+            let subscribers = subscribers.clone();
+
+            move |raw: JsValue| {
+                let mut subscribers = subscribers.borrow_mut();
+                subscribers.retain(|tx| !tx.is_closed());
+
+                for tx in subscribers.iter() {
+                    tx.send(raw.clone());
+                }
+            }
+        });
+        let unlisten = inner::listen(event, &closure).await?;
+
+        Ok(Rc::new(Self {
+            event: event.to_string(),
+            topics,
+            subscribers,
+            unlisten: js_sys::Function::from(unlisten),
+            handler: Some(closure),
+        }))
+    }
+}
+
+impl Drop for Topic {
+    fn drop(&mut self) {
+        if let Some(topics) = self.topics.upgrade() {
+            let mut topics = topics.borrow_mut();
+
+            // only remove our own entry, the event might have been re-registered since
+            if topics
+                .get(&self.event)
+                .is_some_and(|topic| topic.strong_count() == 0)
+            {
+                topics.remove(&self.event);
+            }
+        }
+
+        log::debug!("Calling unlisten for hub topic {}", self.event);
+        unlisten(&self.unlisten, self.handler.take());
+    }
+}
+
+struct Subscription<T> {
+    rx: channel::Receiver<JsValue>,
+    _topic: Rc<Topic>,
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Stream for Subscription<T> {
+    type Item = crate::Result<Event<T>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx
+            .poll_next_unpin(cx)
+            .map(|raw| raw.map(deserialize_event))
+    }
+}
@@ -0,0 +1,283 @@
+//! Request/response style communication between windows, built on top of events.
+//!
+//! One window registers a handler with [`serve`], other windows send requests to it with [`request`].
+//! Requests and responses are broadcast as `rpc:<name>:request` and `rpc:<name>:response` events,
+//! and matched up through correlation ids, so no backend changes are needed.
+//!
+//! The name must include only alphanumeric characters, `-`, `/`, `:` and `_`.
+//!
+//! Windows are identified by their label. Like `appWindow` in the JS API, the current window is assumed to be `main`
+//! if Tauri didn't inject the window metadata, e.g. when running outside of a Tauri window without [`mock_windows`](crate::mocks::mock_windows).
+
+use super::{current_window_label, emit, listen};
+use futures::{
+    future::{self, AbortHandle, Abortable, Either},
+    Future, StreamExt,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{cell::Cell, fmt::Display, time::Duration};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+
+/// The timeout used by [`request`].
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize)]
+struct OutgoingRequest<'a, T> {
+    id: &'a str,
+    from: &'a str,
+    to: &'a str,
+    payload: &'a T,
+}
+
+#[derive(Deserialize)]
+struct IncomingRequest {
+    id: String,
+    from: String,
+    to: String,
+    #[serde(with = "serde_wasm_bindgen::preserve")]
+    payload: JsValue,
+}
+
+#[derive(Serialize)]
+struct OutgoingResponse<'a, T> {
+    id: &'a str,
+    from: &'a str,
+    to: &'a str,
+    result: Result<T, String>,
+}
+
+#[derive(Deserialize)]
+struct IncomingResponse {
+    id: String,
+    #[serde(with = "serde_wasm_bindgen::preserve")]
+    result: JsValue,
+}
+
+/// A registered request handler, see [`serve`].
+///
+/// The handler is unregistered when this is dropped.
+#[derive(Debug)]
+pub struct Server {
+    abort_handle: AbortHandle,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.abort_handle.abort();
+    }
+}
+
+/// A request handler, see [`serve`].
+///
+/// Implemented for all functions taking a request and returning a future that resolves to `Result<Resp, E>`, where `E: Display`.
+pub trait Handler<Req, Resp>: FnMut(Req) -> <Self as Handler<Req, Resp>>::Future + 'static {
+    /// The future returned by the handler.
+    type Future: Future<Output = Result<Resp, Self::Error>> + 'static;
+    /// The error the handler fails with.
+    type Error: Display;
+}
+
+impl<Req, Resp, E, Fut, F> Handler<Req, Resp> for F
+where
+    F: FnMut(Req) -> Fut + 'static,
+    Fut: Future<Output = Result<Resp, E>> + 'static,
+    E: Display,
+{
+    type Future = Fut;
+    type Error = E;
+}
+
+/// Registers a handler for the requests with the given name sent to the current window.
+///
+/// Errors returned by the handler are sent back to the requesting window and surface there as [`Error::Rpc`](crate::Error::Rpc).
+///
+/// The request and response types can be given explicitly as `serve::<Req, Resp>`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::event::rpc::serve;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let server = serve::<(u32, u32), u32>("add", |(a, b)| async move {
+///     a.checked_add(b).ok_or("overflow")
+/// })
+/// .await?;
+///
+/// // requests are served until the server is dropped
+/// # Ok(())
+/// # }
+/// ```
+pub async fn serve<Req, Resp>(
+    name: &str,
+    mut handler: impl Handler<Req, Resp>,
+) -> crate::Result<Server>
+where
+    Req: DeserializeOwned + 'static,
+    Resp: Serialize + 'static,
+{
+    let label = current_label();
+    let response_event = response_event(name);
+    let requests = listen::<IncomingRequest>(&request_event(name)).await?;
+
+    let serve = requests
+        .filter(move |event| future::ready(event.payload.to == label))
+        .for_each_concurrent(None, move |event| {
+            let request = event.payload;
+            let response_event = response_event.clone();
+
+            let res = serde_wasm_bindgen::from_value::<Req>(request.payload)
+                .map(&mut handler)
+                .map_err(|err| err.to_string());
+
+            async move {
+                let result = match res {
+                    Ok(fut) => fut.await.map_err(|err| err.to_string()),
+                    Err(err) => Err(err),
+                };
+
+                let response = OutgoingResponse {
+                    id: &request.id,
+                    from: &request.to,
+                    to: &request.from,
+                    result,
+                };
+
+                if let Err(err) = emit(&response_event, &response).await {
+                    log::error!("Failed to respond to request {}: {}", request.id, err);
+                }
+            }
+        });
+
+    let (abort_handle, abort_registration) = AbortHandle::new_pair();
+    wasm_bindgen_futures::spawn_local(async move {
+        let _ = Abortable::new(serve, abort_registration).await;
+    });
+
+    Ok(Server { abort_handle })
+}
+
+/// Sends a request to the window with the given label and waits for the response, failing after [`DEFAULT_TIMEOUT`].
+///
+/// See [`request_with_timeout`] for details.
+pub async fn request<Req, Resp>(target: &str, name: &str, req: &Req) -> crate::Result<Resp>
+where
+    Req: Serialize,
+    Resp: DeserializeOwned,
+{
+    request_with_timeout(target, name, req, DEFAULT_TIMEOUT).await
+}
+
+/// Sends a request to the window with the given label and waits for the response.
+///
+/// Fails with [`Error::Timeout`](crate::Error::Timeout) if no response arrives in time, e.g. because the target window doesn't serve requests with that name,
+/// and with [`Error::Rpc`](crate::Error::Rpc) if the handler returned an error.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use tauri_sys::event::rpc::request_with_timeout;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let sum: u32 = request_with_timeout("main", "add", &(1, 2), Duration::from_secs(1)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn request_with_timeout<Req, Resp>(
+    target: &str,
+    name: &str,
+    req: &Req,
+    timeout: Duration,
+) -> crate::Result<Resp>
+where
+    Req: Serialize,
+    Resp: DeserializeOwned,
+{
+    let label = current_label();
+    let id = correlation_id(&label);
+
+    // listen before sending the request, so the response can't be missed
+    let responses = listen::<IncomingResponse>(&response_event(name)).await?;
+
+    let request = OutgoingRequest {
+        id: &id,
+        from: &label,
+        to: target,
+        payload: req,
+    };
+    emit(&request_event(name), &request).await?;
+
+    let mut response = responses.filter(|event| future::ready(event.payload.id == id));
+
+    match future::select(response.next(), Box::pin(sleep(timeout))).await {
+        Either::Left((Some(event), _)) => {
+            match serde_wasm_bindgen::from_value::<Result<Resp, String>>(event.payload.result)? {
+                Ok(resp) => Ok(resp),
+                Err(err) => Err(crate::Error::Rpc(err)),
+            }
+        }
+        Either::Left((None, _)) => Err(crate::Error::Rpc(
+            "Response listener closed unexpectedly".to_string(),
+        )),
+        Either::Right(_) => Err(crate::Error::Timeout(timeout)),
+    }
+}
+
+/// The label of the current window, see the [module docs](self).
+fn current_label() -> String {
+    current_window_label().unwrap_or_else(|| "main".to_string())
+}
+
+fn request_event(name: &str) -> String {
+    format!("rpc:{}:request", name)
+}
+
+fn response_event(name: &str) -> String {
+    format!("rpc:{}:response", name)
+}
+
+thread_local! {
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Creates an id that is unique across windows, even if they were reloaded.
+fn correlation_id(label: &str) -> String {
+    let counter = NEXT_ID.with(|id| {
+        let next = id.get();
+        id.set(next + 1);
+        next
+    });
+    let random = (js_sys::Math::random() * u32::MAX as f64) as u32;
+
+    format!("{}:{}:{:08x}", label, counter, random)
+}
+
+/// Resolves after the given duration, the timer is cleared if this is dropped before.
+async fn sleep(duration: Duration) {
+    let mut timer = Timer(JsValue::UNDEFINED);
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        timer.0 = inner::setTimeout(&resolve, duration.as_millis().min(i32::MAX as u128) as i32);
+    });
+
+    let _ = JsFuture::from(promise).await;
+}
+
+struct Timer(JsValue);
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        inner::clearTimeout(&self.0);
+    }
+}
+
+mod inner {
+    use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+    #[wasm_bindgen]
+    extern "C" {
+        pub fn setTimeout(handler: &js_sys::Function, timeout: i32) -> JsValue;
+        pub fn clearTimeout(id: &JsValue);
+    }
+}
@@ -29,7 +29,7 @@ use serde_repr::*;
 use std::path::{Path, PathBuf};
 use std::str;
 
-#[derive(Serialize_repr, Clone, PartialEq, Eq, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, Clone, PartialEq, Eq, Hash, Debug)]
 #[repr(u16)]
 pub enum BaseDirectory {
     Audio = 1,
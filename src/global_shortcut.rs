@@ -39,8 +39,11 @@
 //! ```
 //! It is recommended to allowlist only the APIs you use for optimal bundle size and security.
 
-use futures::{channel::mpsc, Stream, StreamExt};
+use crate::channel;
+pub use crate::channel::{Overflow, StreamConfig};
+use futures::{Stream, StreamExt};
 use wasm_bindgen::{prelude::Closure, JsValue};
+use wasm_bindgen_futures::JsFuture;
 
 /// Determines whether the given shortcut is registered by this application or not.
 ///
@@ -81,29 +84,66 @@ pub async fn is_registered(shortcut: &str) -> crate::Result<bool> {
 /// # }
 /// ```
 pub async fn register(shortcut: &str) -> crate::Result<impl Stream<Item = ()>> {
-    let (tx, rx) = mpsc::unbounded();
+    register_with(shortcut, StreamConfig::default()).await
+}
+
+/// Register a global shortcut, buffering its triggers as configured.
+///
+/// See [`register`] for details.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::global_shortcut::{register_with, StreamConfig};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // triggers while the previous one is still being handled are collapsed into one
+/// let events = register_with("CommandOrControl+R", StreamConfig::latest()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn register_with(
+    shortcut: &str,
+    config: StreamConfig,
+) -> crate::Result<impl Stream<Item = ()>> {
+    let (tx, rx) = channel::channel(config);
 
     let closure = Closure::<dyn FnMut(JsValue)>::new(move |_| {
-        let _ = tx.unbounded_send(());
+        tx.send(());
     });
     inner::register(shortcut, &closure).await?;
-    closure.forget();
 
     Ok(Listen {
         shortcut: JsValue::from_str(shortcut),
         rx,
+        handler: Some(closure),
     })
 }
 
 struct Listen<T> {
     pub shortcut: JsValue,
-    pub rx: mpsc::UnboundedReceiver<T>,
+    pub rx: channel::Receiver<T>,
+    pub handler: Option<Closure<dyn FnMut(JsValue)>>,
 }
 
 impl<T> Drop for Listen<T> {
     fn drop(&mut self) {
         log::debug!("Unregistering shortcut {:?}", self.shortcut);
-        inner::unregister(self.shortcut.clone());
+        let unregistered = JsFuture::from(inner::unregister(self.shortcut.clone()));
+        let handler = self.handler.take();
+
+        // the handler has to outlive the registration, as the backend might still call it until then
+        wasm_bindgen_futures::spawn_local(async move {
+            match unregistered.await {
+                Ok(_) => drop(handler),
+                Err(err) => {
+                    log::error!("Failed to unregister shortcut: {:?}", err);
+                    if let Some(handler) = handler {
+                        handler.forget();
+                    }
+                }
+            }
+        });
     }
 }
 
@@ -194,6 +234,6 @@ mod inner {
         //     shortcuts: Array,
         //     handler: &Closure<dyn FnMut(JsValue)>,
         // ) -> Result<(), JsValue>;
-        pub fn unregister(shortcut: JsValue);
+        pub fn unregister(shortcut: JsValue) -> js_sys::Promise;
     }
 }
@@ -0,0 +1,480 @@
+//! Access the HTTP client written in Rust.
+//!
+//! The APIs must be added to `tauri.allowlist.http` in `tauri.conf.json`:
+//! ```json
+//! {
+//!     "tauri": {
+//!         "allowlist": {
+//!             "http": {
+//!                 "all": true, // enable all http APIs
+//!                 "request": true // enable HTTP request API
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//! It is recommended to allowlist only the APIs you use for optimal bundle size and security.
+//!
+//! ## Security
+//!
+//! This API has a scope configuration that forces you to restrict the URLs and paths that can be accessed using glob patterns.
+//!
+//! For instance, this scope configuration only allows making HTTP requests to the GitHub API for the `tauri-apps` organization:
+//! ```json
+//! {
+//!     "tauri": {
+//!         "allowlist": {
+//!             "http": {
+//!                 "scope": ["https://api.github.com/repos/tauri-apps/*"]
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//! Trying to execute any API with a URL not configured on the scope results in a promise rejection due to denied access.
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_repr::Serialize_repr;
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// The type of the response body.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr)]
+#[repr(u8)]
+pub enum ResponseType {
+    /// The response body is parsed as JSON.
+    #[default]
+    Json = 1,
+    /// The response body is returned as a UTF-8 string.
+    Text = 2,
+    /// The response body is returned as a byte array.
+    Binary = 3,
+}
+
+/// The request HTTP verb.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    #[default]
+    Get,
+    Post,
+    Put,
+    Delete,
+    Patch,
+    Head,
+    Options,
+    Connect,
+    Trace,
+}
+
+/// The body of a request.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum Body {
+    /// A JSON body, usually created through [`Body::json`].
+    Json(#[serde(with = "serde_wasm_bindgen::preserve")] JsValue),
+    /// A plain text body.
+    Text(String),
+    /// A binary body.
+    Bytes(Vec<u8>),
+    /// A `multipart/form-data` body.
+    Form(FormBody),
+}
+
+impl Body {
+    /// Creates a new JSON body from any serializable value.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_sys::http::Body;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Report<'a> {
+    ///     message: &'a str,
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let body = Body::json(&Report { message: "Something bad happened" })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn json<T: Serialize>(data: &T) -> crate::Result<Self> {
+        let raw = data.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?;
+
+        Ok(Self::Json(raw))
+    }
+
+    /// Creates a new plain text body.
+    pub fn text(value: impl Into<String>) -> Self {
+        Self::Text(value.into())
+    }
+
+    /// Creates a new binary body.
+    pub fn bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self::Bytes(bytes.into())
+    }
+
+    /// Creates a new `multipart/form-data` body.
+    pub fn form(form: FormBody) -> Self {
+        Self::Form(form)
+    }
+}
+
+/// The contents of a file form part.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum FilePart {
+    /// Path to a file on disk, read by the backend.
+    Path(PathBuf),
+    /// The contents of the file.
+    Bytes(Vec<u8>),
+}
+
+/// A single part of a [`FormBody`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum FormPart {
+    /// A plain text value.
+    Text(String),
+    /// A binary value.
+    Bytes(Vec<u8>),
+    /// A file, optionally annotated with its mime type and file name.
+    File {
+        #[serde(rename = "file")]
+        path_or_bytes: FilePart,
+        mime: Option<String>,
+        #[serde(rename = "fileName")]
+        file_name: Option<String>,
+    },
+}
+
+/// The body of a `multipart/form-data` request.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::http::{Body, FilePart, FormBody, FormPart};
+///
+/// let mut form = FormBody::new();
+/// form.append("version", FormPart::Text("1.0.0".to_string()))
+///     .append(
+///         "report",
+///         FormPart::File {
+///             path_or_bytes: FilePart::Path("/tmp/crash.log".into()),
+///             mime: Some("text/plain".to_string()),
+///             file_name: Some("crash.log".to_string()),
+///         },
+///     );
+///
+/// let body = Body::form(form);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct FormBody(HashMap<String, FormPart>);
+
+impl FormBody {
+    /// Creates an empty form body.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a part to the form, replacing any previous part with the same name.
+    pub fn append(&mut self, name: impl Into<String>, part: FormPart) -> &mut Self {
+        self.0.insert(name.into(), part);
+        self
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClientOptions {
+    max_redirections: Option<u32>,
+    connect_timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HttpOptions<'a> {
+    method: HttpMethod,
+    url: &'a str,
+    headers: HashMap<&'a str, &'a str>,
+    query: HashMap<&'a str, &'a str>,
+    body: Option<Body>,
+    timeout: Option<Duration>,
+    response_type: ResponseType,
+}
+
+/// The response object.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response<T> {
+    /// The request URL.
+    pub url: String,
+    /// The response status code.
+    pub status: u16,
+    /// A boolean indicating whether the response was successful (status in the range 200–299) or not.
+    pub ok: bool,
+    /// The response headers.
+    pub headers: HashMap<String, String>,
+    /// The response raw headers.
+    pub raw_headers: HashMap<String, Vec<String>>,
+    /// The response data.
+    pub data: T,
+}
+
+/// The client builder.
+///
+/// Configures and creates a new [`Client`].
+#[derive(Debug, Default, Clone)]
+pub struct ClientBuilder {
+    inner: ClientOptions,
+}
+
+impl ClientBuilder {
+    /// Gets the default client builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Defines the maximum number of redirects the client should follow.
+    ///
+    /// If set to 0, no redirects will be followed.
+    pub fn set_max_redirections(&mut self, max_redirections: u32) -> &mut Self {
+        self.inner.max_redirections = Some(max_redirections);
+        self
+    }
+
+    /// The connection timeout.
+    pub fn set_connect_timeout(&mut self, connect_timeout: Duration) -> &mut Self {
+        self.inner.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Creates a new client.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_sys::http::ClientBuilder;
+    /// use std::time::Duration;
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ClientBuilder::new()
+    ///     .set_max_redirections(3)
+    ///     .set_connect_timeout(Duration::from_secs(10))
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build(&self) -> crate::Result<Client> {
+        let opts = self
+            .inner
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())?;
+
+        let raw = inner::getClient(opts).await?;
+
+        Ok(Client(raw.unchecked_into()))
+    }
+}
+
+/// A HTTP client, backed by the HTTP client of the Tauri backend.
+///
+/// The client is dropped in the backend automatically when this value is dropped.
+#[derive(Debug)]
+pub struct Client(inner::Client);
+
+impl Client {
+    /// Creates a new client with the default options.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_sys::http::Client;
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn new() -> crate::Result<Self> {
+        ClientBuilder::new().build().await
+    }
+
+    /// Creates a request builder for the given HTTP method and URL.
+    pub fn request<'a>(&'a self, method: HttpMethod, url: &'a str) -> RequestBuilder<'a> {
+        RequestBuilder {
+            client: self,
+            inner: HttpOptions {
+                method,
+                url,
+                headers: HashMap::new(),
+                query: HashMap::new(),
+                body: None,
+                timeout: None,
+                response_type: ResponseType::default(),
+            },
+        }
+    }
+
+    /// Creates a `GET` request builder.
+    pub fn get<'a>(&'a self, url: &'a str) -> RequestBuilder<'a> {
+        self.request(HttpMethod::Get, url)
+    }
+
+    /// Creates a `POST` request builder.
+    pub fn post<'a>(&'a self, url: &'a str) -> RequestBuilder<'a> {
+        self.request(HttpMethod::Post, url)
+    }
+
+    /// Creates a `PUT` request builder.
+    pub fn put<'a>(&'a self, url: &'a str) -> RequestBuilder<'a> {
+        self.request(HttpMethod::Put, url)
+    }
+
+    /// Creates a `PATCH` request builder.
+    pub fn patch<'a>(&'a self, url: &'a str) -> RequestBuilder<'a> {
+        self.request(HttpMethod::Patch, url)
+    }
+
+    /// Creates a `DELETE` request builder.
+    pub fn delete<'a>(&'a self, url: &'a str) -> RequestBuilder<'a> {
+        self.request(HttpMethod::Delete, url)
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        log::debug!("Dropping http client");
+        self.0.dropClient();
+    }
+}
+
+/// The request builder.
+///
+/// Created through [`Client::request`] or one of the method specific shorthands, e.g. [`Client::get`].
+#[derive(Debug)]
+pub struct RequestBuilder<'a> {
+    client: &'a Client,
+    inner: HttpOptions<'a>,
+}
+
+impl<'a> RequestBuilder<'a> {
+    /// Adds a request header.
+    pub fn set_header(&mut self, key: &'a str, value: &'a str) -> &mut Self {
+        self.inner.headers.insert(key, value);
+        self
+    }
+
+    /// Adds many request headers.
+    pub fn set_headers(
+        &mut self,
+        headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> &mut Self {
+        self.inner.headers.extend(headers);
+        self
+    }
+
+    /// Adds a query parameter.
+    pub fn set_query(&mut self, key: &'a str, value: &'a str) -> &mut Self {
+        self.inner.query.insert(key, value);
+        self
+    }
+
+    /// Adds many query parameters.
+    pub fn set_queries(
+        &mut self,
+        query: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> &mut Self {
+        self.inner.query.extend(query);
+        self
+    }
+
+    /// The request body.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_sys::http::{Body, Client};
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new().await?;
+    ///
+    /// let response = client
+    ///     .post("https://example.com/reports")
+    ///     .set_body(Body::text("Something bad happened"))
+    ///     .send::<()>()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_body(&mut self, body: Body) -> &mut Self {
+        self.inner.body = Some(body);
+        self
+    }
+
+    /// The request timeout.
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.inner.timeout = Some(timeout);
+        self
+    }
+
+    /// The type of the response body. Defaults to [`ResponseType::Json`].
+    ///
+    /// Use [`ResponseType::Text`] together with `String` or [`ResponseType::Binary`] together with `Vec<u8>` as the response data type.
+    pub fn set_response_type(&mut self, response_type: ResponseType) -> &mut Self {
+        self.inner.response_type = response_type;
+        self
+    }
+
+    /// Sends the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tauri_sys::http::{Client, ResponseType};
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new().await?;
+    ///
+    /// let response = client
+    ///     .get("https://api.github.com/repos/tauri-apps/tauri")
+    ///     .set_response_type(ResponseType::Text)
+    ///     .send::<String>()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Requires [`allowlist > http > request`](https://tauri.app/v1/api/config#httpallowlistconfig.request) to be enabled.
+    pub async fn send<T: DeserializeOwned>(&self) -> crate::Result<Response<T>> {
+        let opts = self
+            .inner
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())?;
+
+        let raw = self.client.0.request(opts).await?;
+
+        Ok(serde_wasm_bindgen::from_value(raw)?)
+    }
+}
+
+mod inner {
+    use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+    #[wasm_bindgen(module = "/src/http.js")]
+    extern "C" {
+        #[derive(Debug, Clone, PartialEq)]
+        pub type Client;
+        #[wasm_bindgen(method, catch)]
+        pub async fn request(this: &Client, options: JsValue) -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(method, js_name = drop)]
+        pub fn dropClient(this: &Client);
+    }
+
+    #[wasm_bindgen(module = "/src/http.js")]
+    extern "C" {
+        #[wasm_bindgen(catch)]
+        pub async fn getClient(options: JsValue) -> Result<JsValue, JsValue>;
+    }
+}
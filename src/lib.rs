@@ -79,13 +79,31 @@
 //!
 //! Streams, much like iterators, are poll-based meaning the caller is responsible for advancing it.
 //! This allows greater flexibility as you can freely decide *when* to process events.
-//! Event streams are internally backed by an unbounded queue so events are buffered until read,
+//! Event streams are by default backed by an unbounded queue so events are buffered until read,
 //! so no events are getting lost even if you temporarily pause processing.
 //!
 //! Being unbounded means the memory consumption will grow if the stream is kept around, but not read from.
 //! This is rarely a concern in practice, but if you need to suspend processing of events for a long time,
 //! you should rather drop the entire stream and re-create it as needed later.
 //!
+//! For high-frequency events, such as window moves or progress reports, you can bound the queue instead
+//! using [`event::listen_with`], [`window::WebviewWindow::listen_with`], the `_with` variants of the window streams
+//! like [`window::WebviewWindow::on_moved_with`] or [`global_shortcut::register_with`].
+//! The [`event::StreamConfig`] decides how many events are kept and what happens to events arriving while the queue is full:
+//!
+//! ```rust
+//! use tauri_sys::{
+//!     event::{listen_with, Overflow, StreamConfig},
+//!     window::current_window,
+//! };
+//!
+//! // only keep the most recent position
+//! let positions = current_window().on_moved_with(StreamConfig::latest()).await?;
+//!
+//! // keep the last 32 progress reports
+//! let progress = listen_with::<u32>("progress", StreamConfig::bounded(32, Overflow::DropOldest)).await?;
+//! ```
+//!
 //! ### Cancelling Streams
 //!
 //! One usecase of the `unlisten` function might intuitively not map well to streams: Cancellation.
@@ -120,6 +138,10 @@
 
 #[cfg(feature = "app")]
 pub mod app;
+#[cfg(any(feature = "event", feature = "global_shortcut"))]
+mod channel;
+#[cfg(feature = "cli")]
+pub mod cli;
 #[cfg(feature = "clipboard")]
 pub mod clipboard;
 #[cfg(feature = "dialog")]
@@ -131,6 +153,8 @@ pub mod event;
 pub mod fs;
 #[cfg(feature = "global_shortcut")]
 pub mod global_shortcut;
+#[cfg(feature = "http")]
+pub mod http;
 #[cfg(feature = "mocks")]
 pub mod mocks;
 #[cfg(feature = "notification")]
@@ -139,8 +163,12 @@ pub mod notification;
 pub mod os;
 #[cfg(feature = "path")]
 pub mod path;
+#[cfg(feature = "plugin_log")]
+pub mod plugin_log;
 #[cfg(feature = "process")]
 pub mod process;
+#[cfg(feature = "shell")]
+pub mod shell;
 #[cfg(feature = "tauri")]
 pub mod tauri;
 #[cfg(feature = "updater")]
@@ -149,6 +177,12 @@ pub mod updater;
 pub mod window;
 
 pub use error::Error;
+
+/// Items the exported macros refer to from the code they generate.
+#[doc(hidden)]
+pub mod __private {
+    pub use serde as __tauri_sys_serde;
+}
 pub(crate) type Result<T> = core::result::Result<T, Error>;
 
 #[cfg(any(feature = "dialog", feature = "window"))]
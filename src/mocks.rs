@@ -1,8 +1,20 @@
 //! 
 
 use js_sys::Array;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{cell::RefCell, collections::BTreeMap, fmt::Debug, rc::Rc};
 use wasm_bindgen::{prelude::Closure, JsValue};
 
+#[cfg(feature = "fs")]
+mod fs;
+#[cfg(feature = "window")]
+mod window;
+
+#[cfg(feature = "fs")]
+pub use fs::{FakeEntry, FakeFs};
+#[cfg(feature = "window")]
+pub use window::{FakeWindowManager, WindowState};
+
 /// Mocks the current window label
 /// In non-tauri context it is required to call this function///before* using the `@tauri-apps/api/window` module.
 ///
@@ -45,9 +57,545 @@ where
     closure.forget();
 }
 
+type Handler = Box<dyn FnMut(JsValue) -> Result<JsValue, JsValue>>;
+
+/// Routes mocked IPC calls to typed handlers.
+///
+/// Custom commands are registered with [`MockRouter::command`], calls to the built-in APIs, such as [`app::get_version`](crate::app::get_version),
+/// with [`MockRouter::builtin`] using the module and command names of the Tauri JS API.
+/// Calls without a matching handler are rejected with an error listing the call and all mocked commands.
+///
+/// Unless mocked explicitly, the router answers the `Event` module itself:
+/// listeners are kept in the registry used by [`emit_mock_event`] and events emitted by the frontend are delivered to them.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::{de::IgnoredAny, Deserialize, Serialize};
+/// use tauri_sys::{mocks::MockRouter, tauri};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct AddArgs {
+///     a: u32,
+///     b: u32,
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut router = MockRouter::new();
+/// router
+///     .command::<AddArgs, u32>("add", |args| Ok(args.a + args.b))
+///     .builtin("App", "getAppVersion", |_: IgnoredAny| Ok("1.0.0"));
+/// router.install();
+///
+/// let sum: u32 = tauri::invoke("add", &AddArgs { a: 1, b: 2 }).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct MockRouter {
+    commands: BTreeMap<String, Handler>,
+    builtins: BTreeMap<(String, String), Handler>,
+}
+
+impl MockRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mocks a custom command, i.e. one invoked through [`tauri::invoke`](crate::tauri::invoke).
+    ///
+    /// The handler receives the arguments of the call, the returned value or error is passed back to the caller.
+    /// Plugin commands are registered with their full name, e.g. `plugin:log|log`.
+    pub fn command<A, R>(
+        &mut self,
+        name: &str,
+        mut handler: impl FnMut(A) -> Result<R, JsValue> + 'static,
+    ) -> &mut Self
+    where
+        A: DeserializeOwned,
+        R: Serialize,
+    {
+        let name = name.to_string();
+        let cmd = name.clone();
+
+        self.commands.insert(
+            name,
+            Box::new(move |payload| {
+                let args = serde_wasm_bindgen::from_value(payload).map_err(|err| {
+                    JsValue::from_str(&format!("Invalid arguments for command `{}`: {}", cmd, err))
+                })?;
+
+                serialize_response(handler(args)?)
+            }),
+        );
+
+        self
+    }
+
+    /// Mocks a command of a built-in module, e.g. `("App", "getAppVersion")`.
+    ///
+    /// The handler receives the message sent to the module, which includes the `cmd` field along with the command's arguments.
+    pub fn builtin<M, R>(
+        &mut self,
+        module: &str,
+        cmd: &str,
+        mut handler: impl FnMut(M) -> Result<R, JsValue> + 'static,
+    ) -> &mut Self
+    where
+        M: DeserializeOwned,
+        R: Serialize,
+    {
+        let key = (module.to_string(), cmd.to_string());
+        let name = format!("{}.{}", module, cmd);
+
+        self.builtins.insert(
+            key,
+            Box::new(move |message| {
+                let message = serde_wasm_bindgen::from_value(message).map_err(|err| {
+                    JsValue::from_str(&format!("Invalid message for `{}`: {}", name, err))
+                })?;
+
+                serialize_response(handler(message)?)
+            }),
+        );
+
+        self
+    }
+
+    /// Mocks a command of a built-in module like [`MockRouter::builtin`], passing the message and response through as-is.
+    ///
+    /// Useful when the message or response can't be expressed as a serde type, e.g. because it holds a JS object.
+    pub fn builtin_raw(
+        &mut self,
+        module: &str,
+        cmd: &str,
+        handler: impl FnMut(JsValue) -> Result<JsValue, JsValue> + 'static,
+    ) -> &mut Self {
+        let key = (module.to_string(), cmd.to_string());
+        self.builtins.insert(key, Box::new(handler));
+
+        self
+    }
+
+    /// Intercepts all IPC requests with this router, see [`mock_ipc`].
+    pub fn install(self) {
+        mock_ipc(self.into_handler());
+    }
+
+    /// Turns the router into a handler for [`mock_ipc`], e.g. to wrap it with an [`IpcRecorder`].
+    pub fn into_handler(mut self) -> impl FnMut(String, JsValue) -> Result<JsValue, JsValue> {
+        move |cmd, payload| self.handle(cmd, payload)
+    }
+
+    fn handle(&mut self, cmd: String, payload: JsValue) -> Result<JsValue, JsValue> {
+        if let Some((module, message_cmd, message)) = builtin_message(&cmd, &payload) {
+            let is_event = module == "Event";
+
+            return match self.builtins.get_mut(&(module, message_cmd)) {
+                Some(handler) => handler(message),
+                None if is_event => handle_event_message(message),
+                None => Err(self.unmocked(&cmd, &payload)),
+            };
+        }
+
+        match self.commands.get_mut(&cmd) {
+            Some(handler) => handler(payload),
+            None => Err(self.unmocked(&cmd, &payload)),
+        }
+    }
+
+    fn unmocked(&self, cmd: &str, payload: &JsValue) -> JsValue {
+        let payload = js_sys::JSON::stringify(payload)
+            .ok()
+            .and_then(|payload| payload.as_string())
+            .unwrap_or_else(|| format!("{:?}", payload));
+
+        let commands: Vec<&str> = self.commands.keys().map(String::as_str).collect();
+        let builtins: Vec<String> = self
+            .builtins
+            .keys()
+            .map(|(module, cmd)| format!("{}.{}", module, cmd))
+            .collect();
+
+        JsValue::from_str(&format!(
+            "Unmocked command `{}` called with {}. Mocked commands: [{}], mocked built-ins: [{}]",
+            cmd,
+            payload,
+            commands.join(", "),
+            builtins.join(", ")
+        ))
+    }
+}
+
+/// A call intercepted by an [`IpcRecorder`].
+#[derive(Debug, Clone)]
+pub struct IpcCall {
+    /// The name of the command.
+    ///
+    /// Calls to the built-in APIs are named after their module and command, e.g. `App.getAppVersion`.
+    pub cmd: String,
+    /// The arguments of a custom command or the message sent to a built-in module.
+    pub payload: JsValue,
+}
+
+impl IpcCall {
+    /// Deserializes the payload of the call.
+    pub fn args<A: DeserializeOwned>(&self) -> crate::Result<A> {
+        Ok(serde_wasm_bindgen::from_value(self.payload.clone())?)
+    }
+}
+
+/// Records the IPC calls passed to a mock handler.
+///
+/// Recorders are cheap to clone, clones share their recorded calls.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::{de::IgnoredAny, Deserialize, Serialize};
+/// use tauri_sys::{mocks::{IpcRecorder, MockRouter}, tauri};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct AddArgs {
+///     a: u32,
+///     b: u32,
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut router = MockRouter::new();
+/// router.command::<AddArgs, u32>("add", |args| Ok(args.a + args.b));
+///
+/// let recorder = IpcRecorder::new();
+/// recorder.install(router.into_handler());
+///
+/// tauri::invoke::<_, u32>("add", &AddArgs { a: 1, b: 2 }).await?;
+///
+/// recorder.assert_call_count("add", 1);
+/// recorder.assert_called_with("add", &AddArgs { a: 1, b: 2 });
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IpcRecorder {
+    calls: Rc<RefCell<Vec<IpcCall>>>,
+}
+
+impl IpcRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps the given mock handler, recording every call before passing it on.
+    pub fn wrap<H, R, E>(
+        &self,
+        mut handler: H,
+    ) -> Box<dyn FnMut(String, JsValue) -> Result<JsValue, JsValue>>
+    where
+        H: FnMut(String, JsValue) -> Result<R, E> + 'static,
+        R: Into<JsValue>,
+        E: Into<JsValue>,
+    {
+        let calls = self.calls.clone();
+
+        Box::new(move |cmd, payload| {
+            let call = match builtin_message(&cmd, &payload) {
+                Some((module, message_cmd, message)) => IpcCall {
+                    cmd: format!("{}.{}", module, message_cmd),
+                    payload: message,
+                },
+                None => IpcCall {
+                    cmd: cmd.clone(),
+                    payload: payload.clone(),
+                },
+            };
+            calls.borrow_mut().push(call);
+
+            handler(cmd, payload).map(Into::into).map_err(Into::into)
+        })
+    }
+
+    /// Intercepts all IPC requests with the given mock handler, recording every call.
+    ///
+    /// See [`mock_ipc`] for details.
+    pub fn install<H, R, E>(&self, handler: H)
+    where
+        H: FnMut(String, JsValue) -> Result<R, E> + 'static,
+        R: Into<JsValue>,
+        E: Into<JsValue>,
+    {
+        mock_ipc(self.wrap(handler));
+    }
+
+    /// Returns the recorded calls in the order they were made.
+    pub fn calls(&self) -> Vec<IpcCall> {
+        self.calls.borrow().clone()
+    }
+
+    /// Returns the recorded calls and clears the recording.
+    pub fn take_calls(&self) -> Vec<IpcCall> {
+        std::mem::take(&mut *self.calls.borrow_mut())
+    }
+
+    /// Asserts that the command was called with the expected arguments at least once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no recorded call of the command has arguments equal to `expected`.
+    #[track_caller]
+    pub fn assert_called_with<A>(&self, cmd: &str, expected: &A)
+    where
+        A: DeserializeOwned + PartialEq + Debug,
+    {
+        let calls: Vec<Result<A, crate::Error>> = self
+            .calls
+            .borrow()
+            .iter()
+            .filter(|call| call.cmd == cmd)
+            .map(IpcCall::args)
+            .collect();
+
+        if !calls.iter().any(|args| args.as_ref() == Ok(expected)) {
+            panic!(
+                "expected `{}` to be called with {:?}, but it was called with {:?}",
+                cmd, expected, calls
+            );
+        }
+    }
+
+    /// Asserts that the command was called exactly `n` times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of recorded calls of the command differs from `n`.
+    #[track_caller]
+    pub fn assert_call_count(&self, cmd: &str, n: usize) {
+        let count = self
+            .calls
+            .borrow()
+            .iter()
+            .filter(|call| call.cmd == cmd)
+            .count();
+
+        assert_eq!(
+            count, n,
+            "expected `{}` to be called {} times, but it was called {} times",
+            cmd, n, count
+        );
+    }
+}
+
+/// Splits a call to a built-in module into the module, the command and the message sent to the module.
+fn builtin_message(cmd: &str, payload: &JsValue) -> Option<(String, String, JsValue)> {
+    if cmd != "tauri" {
+        return None;
+    }
+
+    let module = js_sys::Reflect::get(payload, &"__tauriModule".into()).ok()?;
+    let message = js_sys::Reflect::get(payload, &"message".into()).ok()?;
+    let message_cmd = js_sys::Reflect::get(&message, &"cmd".into()).ok()?;
+
+    Some((module.as_string()?, message_cmd.as_string()?, message))
+}
+
+fn serialize_response<R: Serialize>(res: R) -> Result<JsValue, JsValue> {
+    res.serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(Into::into)
+}
+
+struct MockListener {
+    id: u32,
+    event: String,
+    window_label: Option<String>,
+    handler: u32,
+}
+
+#[derive(Default)]
+struct MockEvents {
+    next_id: u32,
+    listeners: Vec<MockListener>,
+}
+
+thread_local! {
+    static EVENTS: RefCell<MockEvents> = RefCell::new(MockEvents::default());
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MockEvent<'a> {
+    event: &'a str,
+    window_label: Option<&'a str>,
+    id: u32,
+    #[serde(with = "serde_wasm_bindgen::preserve")]
+    payload: JsValue,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListenMessage {
+    event: String,
+    window_label: Option<String>,
+    handler: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UnlistenMessage {
+    event_id: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EmitMessage {
+    event: String,
+    window_label: Option<String>,
+}
+
+/// Emits an event to the listeners registered through a [`MockRouter`].
+///
+/// Events without a window label are delivered to all listeners of the event,
+/// events with a window label only to the global listeners and those registered for that window.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::{event::listen, mocks::{emit_mock_event, MockRouter}};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// MockRouter::new().install();
+///
+/// let mut events = listen::<u32>("progress").await?;
+/// emit_mock_event("progress", &50, None)?;
+///
+/// assert_eq!(events.next().await.unwrap().payload, 50);
+/// # Ok(())
+/// # }
+/// ```
+pub fn emit_mock_event<T: Serialize>(
+    event: &str,
+    payload: &T,
+    window_label: Option<&str>,
+) -> crate::Result<()> {
+    let payload = payload.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?;
+
+    dispatch_mock_event(event, window_label, payload)
+}
+
+/// Returns the number of mocked listeners registered for the event.
+pub fn listener_count(event: &str) -> usize {
+    EVENTS.with(|events| {
+        events
+            .borrow()
+            .listeners
+            .iter()
+            .filter(|listener| listener.event == event)
+            .count()
+    })
+}
+
+fn dispatch_mock_event(
+    event: &str,
+    window_label: Option<&str>,
+    payload: JsValue,
+) -> crate::Result<()> {
+    // collect the handlers first, as they might unlisten while being called
+    let targets: Vec<(u32, u32)> = EVENTS.with(|events| {
+        events
+            .borrow()
+            .listeners
+            .iter()
+            .filter(|listener| listener.event == event)
+            .filter(|listener| match (window_label, &listener.window_label) {
+                (Some(target), Some(label)) => target == label,
+                _ => true,
+            })
+            .map(|listener| (listener.id, listener.handler))
+            .collect()
+    });
+
+    for (id, handler) in targets {
+        let raw = MockEvent {
+            event,
+            window_label,
+            id,
+            payload: payload.clone(),
+        }
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())?;
+
+        let handler = js_sys::Reflect::get(&js_sys::global(), &format!("_{}", handler).into())?;
+        js_sys::Function::from(handler).call1(&JsValue::NULL, &raw)?;
+    }
+
+    Ok(())
+}
+
+/// Dispatches an event emitted while handling a mocked IPC call.
+///
+/// The listeners might call back into the mocked IPC, so they are called once the current call returned.
+fn dispatch_deferred(event: String, window_label: Option<String>, payload: JsValue) {
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = dispatch_mock_event(&event, window_label.as_deref(), payload) {
+            log::error!("Failed to dispatch mocked event {}: {:?}", event, err);
+        }
+    });
+}
+
+fn handle_event_message(message: JsValue) -> Result<JsValue, JsValue> {
+    let cmd = js_sys::Reflect::get(&message, &"cmd".into())?;
+
+    match cmd.as_string().as_deref() {
+        Some("listen") => {
+            let message: ListenMessage = serde_wasm_bindgen::from_value(message)?;
+
+            let id = EVENTS.with(|events| {
+                let mut events = events.borrow_mut();
+                events.next_id += 1;
+
+                let id = events.next_id;
+                events.listeners.push(MockListener {
+                    id,
+                    event: message.event,
+                    window_label: message.window_label,
+                    handler: message.handler,
+                });
+
+                id
+            });
+
+            Ok(JsValue::from(id))
+        }
+        Some("unlisten") => {
+            let message: UnlistenMessage = serde_wasm_bindgen::from_value(message)?;
+
+            EVENTS.with(|events| {
+                events
+                    .borrow_mut()
+                    .listeners
+                    .retain(|listener| listener.id != message.event_id)
+            });
+
+            Ok(JsValue::NULL)
+        }
+        Some("emit") => {
+            let payload = js_sys::Reflect::get(&message, &"payload".into())?;
+            let message: EmitMessage = serde_wasm_bindgen::from_value(message)?;
+
+            dispatch_deferred(message.event, message.window_label, payload);
+
+            Ok(JsValue::NULL)
+        }
+        _ => Err(JsValue::from_str(&format!(
+            "Unmocked command `Event.{}`",
+            cmd.as_string().unwrap_or_default()
+        ))),
+    }
+}
+
 /// Clears mocked functions/data injected by the other functions in this module.
 /// When using a test runner that doesn't provide a fresh window object for each test, calling this function will reset tauri specific properties.
 pub fn clear_mocks() {
+    EVENTS.with(|events| events.borrow_mut().listeners.clear());
+
     inner::clearMocks()
 }
 
@@ -0,0 +1,446 @@
+use super::MockRouter;
+use crate::fs::BaseDirectory;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    path::{Component, Path, PathBuf},
+    rc::Rc,
+};
+use wasm_bindgen::JsValue;
+
+const NOT_FOUND: (&str, i32) = ("No such file or directory", 2);
+const EXISTS: (&str, i32) = ("File exists", 17);
+const NOT_A_DIRECTORY: (&str, i32) = ("Not a directory", 20);
+const IS_A_DIRECTORY: (&str, i32) = ("Is a directory", 21);
+const INVALID_ARGUMENT: (&str, i32) = ("Invalid argument", 22);
+const NOT_EMPTY: (&str, i32) = ("Directory not empty", 39);
+
+type Tree = BTreeMap<PathBuf, FakeEntry>;
+
+/// An entry of a [`FakeFs`], as returned by [`FakeFs::snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FakeEntry {
+    /// A file with its contents.
+    File(Vec<u8>),
+    /// A directory. Its children are the entries whose path starts with the directory's path.
+    Dir,
+}
+
+/// An in-memory file system answering the `Fs` module calls of [`fs`](crate::fs).
+///
+/// Every [`BaseDirectory`] has its own tree, paths are resolved relative to it.
+/// Failing operations are rejected with the error messages of the operating system, e.g. for missing paths or non-empty directories.
+///
+/// File systems are cheap to clone, clones share their trees.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::path::Path;
+/// use tauri_sys::{fs::{self, BaseDirectory}, mocks::{FakeFs, MockRouter}};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let files = FakeFs::new();
+/// files.file(BaseDirectory::AppConfig, "settings.json", "{}");
+///
+/// let mut router = MockRouter::new();
+/// files.attach(&mut router);
+/// router.install();
+///
+/// let settings = fs::read_text_file(Path::new("settings.json"), BaseDirectory::AppConfig).await?;
+/// fs::write_text_file(Path::new("settings.json"), r#"{"theme":"dark"}"#, BaseDirectory::AppConfig).await?;
+///
+/// assert_eq!(
+///     files.contents(BaseDirectory::AppConfig, "settings.json").unwrap(),
+///     br#"{"theme":"dark"}"#
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FakeFs {
+    trees: Rc<RefCell<HashMap<Option<BaseDirectory>, Tree>>>,
+}
+
+impl FakeFs {
+    /// Creates a file system without any files.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, creating its parent directories as needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the parent directories is a file.
+    pub fn file(
+        &self,
+        dir: BaseDirectory,
+        path: impl AsRef<Path>,
+        contents: impl Into<Vec<u8>>,
+    ) -> &Self {
+        let path = normalize(path.as_ref());
+        let mut trees = self.trees.borrow_mut();
+        let tree = trees.entry(Some(dir)).or_default();
+
+        if let Some(parent) = path.parent() {
+            create_dir_all(tree, parent).expect("parent directory is a file");
+        }
+        tree.insert(path, FakeEntry::File(contents.into()));
+
+        self
+    }
+
+    /// Adds a directory, creating its parent directories as needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the directory or one of its parents is a file.
+    pub fn dir(&self, dir: BaseDirectory, path: impl AsRef<Path>) -> &Self {
+        let path = normalize(path.as_ref());
+        let mut trees = self.trees.borrow_mut();
+
+        create_dir_all(trees.entry(Some(dir)).or_default(), &path)
+            .expect("directory or parent directory is a file");
+
+        self
+    }
+
+    /// Returns the contents of a file, `None` if the file doesn't exist.
+    pub fn contents(&self, dir: BaseDirectory, path: impl AsRef<Path>) -> Option<Vec<u8>> {
+        let path = normalize(path.as_ref());
+
+        match self.trees.borrow().get(&Some(dir))?.get(&path)? {
+            FakeEntry::File(contents) => Some(contents.clone()),
+            FakeEntry::Dir => None,
+        }
+    }
+
+    /// Returns all entries of a base directory, keyed by their path.
+    pub fn snapshot(&self, dir: BaseDirectory) -> BTreeMap<PathBuf, FakeEntry> {
+        self.trees
+            .borrow()
+            .get(&Some(dir))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Routes the `Fs` module calls of the router to this file system.
+    pub fn attach(&self, router: &mut MockRouter) {
+        self.route(router, "readTextFile", |tree, message: PathMessage| {
+            let contents = read_file(tree, &message.path)?;
+
+            String::from_utf8(contents)
+                .map_err(|_| JsValue::from_str("stream did not contain valid UTF-8"))
+        });
+        self.route(router, "readFile", |tree, message: PathMessage| {
+            read_file(tree, &message.path)
+        });
+        self.route(router, "writeFile", |tree, message: WriteMessage| {
+            let path = normalize(&message.path);
+
+            if is_dir(tree, &path) {
+                return Err(error(IS_A_DIRECTORY, &path));
+            }
+            ensure_parent(tree, &path)?;
+            tree.insert(path, FakeEntry::File(message.contents));
+
+            Ok(())
+        });
+        self.route(router, "readDir", |tree, message: PathMessage| {
+            let path = normalize(&message.path);
+
+            match tree.get(&path) {
+                Some(FakeEntry::File(_)) => Err(error(NOT_A_DIRECTORY, &path)),
+                None if !path.as_os_str().is_empty() => Err(error(NOT_FOUND, &path)),
+                _ => Ok(read_dir(
+                    tree,
+                    &path,
+                    message.options.recursive.unwrap_or_default(),
+                )),
+            }
+        });
+        self.route(router, "copyFile", |tree, message: CopyMessage| {
+            let contents = read_file(tree, &message.source)?;
+            let destination = normalize(&message.destination);
+
+            if is_dir(tree, &destination) {
+                return Err(error(IS_A_DIRECTORY, &destination));
+            }
+            ensure_parent(tree, &destination)?;
+            tree.insert(destination, FakeEntry::File(contents));
+
+            Ok(())
+        });
+        self.route(router, "createDir", |tree, message: PathMessage| {
+            let path = normalize(&message.path);
+
+            if message.options.recursive.unwrap_or_default() {
+                create_dir_all(tree, &path)?;
+            } else {
+                if path.as_os_str().is_empty() || tree.contains_key(&path) {
+                    return Err(error(EXISTS, &path));
+                }
+                ensure_parent(tree, &path)?;
+                tree.insert(path, FakeEntry::Dir);
+            }
+
+            Ok(())
+        });
+        self.route(router, "removeDir", |tree, message: PathMessage| {
+            let path = normalize(&message.path);
+
+            match tree.get(&path) {
+                None => return Err(error(NOT_FOUND, &path)),
+                Some(FakeEntry::File(_)) => return Err(error(NOT_A_DIRECTORY, &path)),
+                Some(FakeEntry::Dir) => {}
+            }
+
+            let descendants: Vec<PathBuf> = tree
+                .keys()
+                .filter(|entry| entry.starts_with(&path) && **entry != path)
+                .cloned()
+                .collect();
+
+            if !descendants.is_empty() && !message.options.recursive.unwrap_or_default() {
+                return Err(error(NOT_EMPTY, &path));
+            }
+
+            for entry in descendants {
+                tree.remove(&entry);
+            }
+            tree.remove(&path);
+
+            Ok(())
+        });
+        self.route(router, "removeFile", |tree, message: PathMessage| {
+            let path = normalize(&message.path);
+
+            match tree.get(&path) {
+                Some(FakeEntry::File(_)) => {
+                    tree.remove(&path);
+                    Ok(())
+                }
+                Some(FakeEntry::Dir) => Err(error(IS_A_DIRECTORY, &path)),
+                None => Err(error(NOT_FOUND, &path)),
+            }
+        });
+        self.route(router, "renameFile", |tree, message: RenameMessage| {
+            let old_path = normalize(&message.old_path);
+            let new_path = normalize(&message.new_path);
+
+            let is_dir = match tree.get(&old_path) {
+                None => return Err(error(NOT_FOUND, &old_path)),
+                Some(entry) => matches!(entry, FakeEntry::Dir),
+            };
+            if new_path == old_path {
+                return Ok(());
+            }
+            // a directory can't be moved into itself
+            if new_path.starts_with(&old_path) {
+                return Err(error(INVALID_ARGUMENT, &new_path));
+            }
+
+            // like `rename(2)`, files and empty directories are replaced by an entry of the same kind
+            match (tree.get(&new_path), is_dir) {
+                (None, _) => ensure_parent(tree, &new_path)?,
+                (Some(FakeEntry::Dir), false) => return Err(error(IS_A_DIRECTORY, &new_path)),
+                (Some(FakeEntry::File(_)), true) => return Err(error(NOT_A_DIRECTORY, &new_path)),
+                (Some(FakeEntry::Dir), true)
+                    if tree
+                        .keys()
+                        .any(|entry| entry.starts_with(&new_path) && *entry != new_path) =>
+                {
+                    return Err(error(NOT_EMPTY, &new_path));
+                }
+                (Some(_), _) => {
+                    tree.remove(&new_path);
+                }
+            }
+
+            // directories are moved along with their contents
+            let moved: Vec<PathBuf> = tree
+                .keys()
+                .filter(|entry| entry.starts_with(&old_path))
+                .cloned()
+                .collect();
+
+            for entry in moved {
+                if let Some(node) = tree.remove(&entry) {
+                    let suffix = entry.strip_prefix(&old_path).unwrap_or(Path::new(""));
+                    tree.insert(new_path.join(suffix), node);
+                }
+            }
+
+            Ok(())
+        });
+        self.route(router, "exists", |tree, message: PathMessage| {
+            let path = normalize(&message.path);
+
+            Ok(path.as_os_str().is_empty() || tree.contains_key(&path))
+        });
+    }
+
+    fn route<M, R>(
+        &self,
+        router: &mut MockRouter,
+        cmd: &str,
+        handler: impl Fn(&mut Tree, M) -> Result<R, JsValue> + 'static,
+    ) where
+        M: DeserializeOwned + HasOptions,
+        R: Serialize,
+    {
+        let trees = self.trees.clone();
+
+        router.builtin("Fs", cmd, move |message: M| {
+            let dir = message.options().dir.clone();
+            let mut trees = trees.borrow_mut();
+
+            handler(trees.entry(dir).or_default(), message)
+        });
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct FsOptions {
+    dir: Option<BaseDirectory>,
+    recursive: Option<bool>,
+}
+
+trait HasOptions {
+    fn options(&self) -> &FsOptions;
+}
+
+#[derive(Deserialize)]
+struct PathMessage {
+    path: PathBuf,
+    #[serde(default)]
+    options: FsOptions,
+}
+
+#[derive(Deserialize)]
+struct WriteMessage {
+    path: PathBuf,
+    contents: Vec<u8>,
+    #[serde(default)]
+    options: FsOptions,
+}
+
+#[derive(Deserialize)]
+struct CopyMessage {
+    source: PathBuf,
+    destination: PathBuf,
+    #[serde(default)]
+    options: FsOptions,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RenameMessage {
+    old_path: PathBuf,
+    new_path: PathBuf,
+    #[serde(default)]
+    options: FsOptions,
+}
+
+macro_rules! impl_has_options {
+    ($($message:ty),*) => {
+        $(
+            impl HasOptions for $message {
+                fn options(&self) -> &FsOptions {
+                    &self.options
+                }
+            }
+        )*
+    };
+}
+
+impl_has_options!(PathMessage, WriteMessage, CopyMessage, RenameMessage);
+
+#[derive(Serialize)]
+struct FileEntry {
+    path: PathBuf,
+    name: Option<String>,
+    children: Option<Vec<FileEntry>>,
+}
+
+/// Resolves `.` and `..` components, the root of a base directory is the empty path.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => normalized.push(name),
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => {}
+        }
+    }
+
+    normalized
+}
+
+fn error((message, code): (&str, i32), path: &Path) -> JsValue {
+    JsValue::from_str(&format!(
+        "{} (os error {}): {}",
+        message,
+        code,
+        path.display()
+    ))
+}
+
+fn is_dir(tree: &Tree, path: &Path) -> bool {
+    path.as_os_str().is_empty() || matches!(tree.get(path), Some(FakeEntry::Dir))
+}
+
+fn ensure_parent(tree: &Tree, path: &Path) -> Result<(), JsValue> {
+    match path.parent() {
+        Some(parent) if !is_dir(tree, parent) => Err(error(NOT_FOUND, parent)),
+        _ => Ok(()),
+    }
+}
+
+fn create_dir_all(tree: &mut Tree, path: &Path) -> Result<(), JsValue> {
+    if let Some(file) = path
+        .ancestors()
+        .find(|ancestor| matches!(tree.get(*ancestor), Some(FakeEntry::File(_))))
+    {
+        return Err(error(EXISTS, file));
+    }
+
+    for ancestor in path.ancestors() {
+        if !ancestor.as_os_str().is_empty() {
+            tree.insert(ancestor.to_path_buf(), FakeEntry::Dir);
+        }
+    }
+
+    Ok(())
+}
+
+fn read_file(tree: &Tree, path: &Path) -> Result<Vec<u8>, JsValue> {
+    let path = normalize(path);
+
+    match tree.get(&path) {
+        Some(FakeEntry::File(contents)) => Ok(contents.clone()),
+        Some(FakeEntry::Dir) => Err(error(IS_A_DIRECTORY, &path)),
+        None => Err(error(NOT_FOUND, &path)),
+    }
+}
+
+fn read_dir(tree: &Tree, path: &Path, recursive: bool) -> Vec<FileEntry> {
+    tree.iter()
+        .filter(|(entry, _)| entry.parent() == Some(path))
+        .map(|(entry, node)| FileEntry {
+            path: entry.clone(),
+            name: entry
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            children: match node {
+                FakeEntry::Dir if recursive => Some(read_dir(tree, entry, recursive)),
+                _ => None,
+            },
+        })
+        .collect()
+}
@@ -0,0 +1,443 @@
+use super::{dispatch_deferred, mock_windows, serialize_response, MockRouter};
+use crate::window::Theme;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// The state of a window managed by a [`FakeWindowManager`].
+///
+/// Sizes and positions are in physical pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowState {
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,
+    pub y: i32,
+    pub scale_factor: f64,
+    pub maximized: bool,
+    pub minimized: bool,
+    pub fullscreen: bool,
+    pub visible: bool,
+    pub focused: bool,
+    pub decorations: bool,
+    pub resizable: bool,
+    pub maximizable: bool,
+    pub minimizable: bool,
+    pub closable: bool,
+    pub always_on_top: bool,
+    pub theme: Theme,
+}
+
+impl Default for WindowState {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            width: 800,
+            height: 600,
+            x: 0,
+            y: 0,
+            scale_factor: 1.0,
+            maximized: false,
+            minimized: false,
+            fullscreen: false,
+            visible: true,
+            focused: false,
+            decorations: true,
+            resizable: true,
+            maximizable: true,
+            minimizable: true,
+            closable: true,
+            always_on_top: false,
+            theme: Theme::Light,
+        }
+    }
+}
+
+/// Keeps the state of mocked windows and answers the `Window` module calls of [`WebviewWindow`](crate::window::WebviewWindow).
+///
+/// Changes to the size, position or focus of a window emit the matching `tauri://resize`, `tauri://move`, `tauri://focus` and `tauri://blur` events
+/// to the listeners registered through the [`MockRouter`], so streams like [`WebviewWindow::on_resized`](crate::window::WebviewWindow::on_resized) can be tested as well.
+/// Maximizing a window or making it fullscreen keeps its tracked size, but emits `tauri://resize` like a real window would.
+///
+/// Windows created with [`WebviewWindowBuilder`](crate::window::WebviewWindowBuilder) are added with the size, position and flags passed to the builder.
+///
+/// Managers are cheap to clone, clones share their windows.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::{mocks::{FakeWindowManager, MockRouter}, window::{current_window, PhysicalSize}};
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let windows = FakeWindowManager::new("main", &[]);
+///
+/// let mut router = MockRouter::new();
+/// windows.attach(&mut router);
+/// router.install();
+///
+/// current_window().set_size(PhysicalSize::new(1024, 768)).await?;
+///
+/// assert_eq!(windows.state("main").unwrap().width, 1024);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FakeWindowManager {
+    windows: Rc<RefCell<BTreeMap<String, WindowState>>>,
+}
+
+impl FakeWindowManager {
+    /// Mocks the given windows with default state, see [`mock_windows`].
+    ///
+    /// The current window starts out focused.
+    pub fn new(current: &str, additional_windows: &[&str]) -> Self {
+        mock_windows(current, additional_windows);
+
+        let windows = std::iter::once(current)
+            .chain(additional_windows.iter().copied())
+            .map(|label| {
+                let state = WindowState {
+                    title: label.to_string(),
+                    focused: label == current,
+                    ..Default::default()
+                };
+
+                (label.to_string(), state)
+            })
+            .collect();
+
+        Self {
+            windows: Rc::new(RefCell::new(windows)),
+        }
+    }
+
+    /// Replaces the state of a window, adding the window if it doesn't exist yet.
+    ///
+    /// No events are emitted.
+    pub fn set_state(&self, label: &str, state: WindowState) -> &Self {
+        self.windows.borrow_mut().insert(label.to_string(), state);
+        self
+    }
+
+    /// Returns the current state of a window, `None` if the window doesn't exist or was closed.
+    pub fn state(&self, label: &str) -> Option<WindowState> {
+        self.windows.borrow().get(label).cloned()
+    }
+
+    /// Routes the `Window` module calls of the router to this manager.
+    pub fn attach(&self, router: &mut MockRouter) {
+        let manager = self.clone();
+        let creator = self.clone();
+
+        router
+            .builtin_raw("Window", "manage", move |message| {
+                let message: ManageMessage = serde_wasm_bindgen::from_value(message)?;
+                manager.handle(message.data)
+            })
+            .builtin(
+                "Window",
+                "createWebview",
+                move |message: CreateWebviewMessage| creator.create(message.data.options),
+            );
+    }
+
+    fn create(&self, options: WindowOptions) -> Result<(), JsValue> {
+        let mut windows = self.windows.borrow_mut();
+        if windows.contains_key(&options.label) {
+            return Err(JsValue::from_str(&format!(
+                "a window with label `{}` already exists",
+                options.label
+            )));
+        }
+
+        let defaults = WindowState::default();
+        let state = WindowState {
+            title: options.title.unwrap_or_else(|| options.label.clone()),
+            width: options
+                .width
+                .map_or(defaults.width, |width| width.round() as u32),
+            height: options
+                .height
+                .map_or(defaults.height, |height| height.round() as u32),
+            x: options.x.map_or(defaults.x, |x| x.round() as i32),
+            y: options.y.map_or(defaults.y, |y| y.round() as i32),
+            maximized: options.maximized.unwrap_or(defaults.maximized),
+            fullscreen: options.fullscreen.unwrap_or(defaults.fullscreen),
+            visible: options.visible.unwrap_or(defaults.visible),
+            decorations: options.decorations.unwrap_or(defaults.decorations),
+            resizable: options.resizable.unwrap_or(defaults.resizable),
+            maximizable: options.maximizable.unwrap_or(defaults.maximizable),
+            minimizable: options.minimizable.unwrap_or(defaults.minimizable),
+            closable: options.closable.unwrap_or(defaults.closable),
+            always_on_top: options.always_on_top.unwrap_or(defaults.always_on_top),
+            theme: options.theme.unwrap_or(defaults.theme),
+            ..defaults
+        };
+
+        windows.insert(options.label.clone(), state);
+        set_registered(&options.label, true)?;
+
+        // the window itself emits `tauri://created` once this resolves
+        if options.focus.unwrap_or(true) {
+            focus(&mut windows, &options.label)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle(&self, data: ManageData) -> Result<JsValue, JsValue> {
+        let kind = js_sys::Reflect::get(&data.cmd, &"type".into())?
+            .as_string()
+            .unwrap_or_default();
+
+        match kind.as_str() {
+            "currentMonitor" | "primaryMonitor" => return Ok(JsValue::NULL),
+            "availableMonitors" => return Ok(js_sys::Array::new().into()),
+            _ => {}
+        }
+
+        let label = data.label.unwrap_or_default();
+        let mut windows = self.windows.borrow_mut();
+        let Some(state) = windows.get_mut(&label) else {
+            return Err(JsValue::from_str(&format!("window `{}` not found", label)));
+        };
+
+        match kind.as_str() {
+            "scaleFactor" => serialize_response(state.scale_factor),
+            "innerPosition" | "outerPosition" => serialize_response(PositionPayload {
+                x: state.x,
+                y: state.y,
+            }),
+            "innerSize" | "outerSize" => serialize_response(SizePayload {
+                width: state.width,
+                height: state.height,
+            }),
+            "isFullscreen" => serialize_response(state.fullscreen),
+            "isMinimized" => serialize_response(state.minimized),
+            "isMaximized" => serialize_response(state.maximized),
+            "isFocused" => serialize_response(state.focused),
+            "isDecorated" => serialize_response(state.decorations),
+            "isResizable" => serialize_response(state.resizable),
+            "isMaximizable" => serialize_response(state.maximizable),
+            "isMinimizable" => serialize_response(state.minimizable),
+            "isClosable" => serialize_response(state.closable),
+            "isVisible" => serialize_response(state.visible),
+            "title" => serialize_response(&state.title),
+            "theme" => serialize_response(&state.theme),
+            "setResizable" => set(&mut state.resizable, payload(&data.cmd)?),
+            "setMaximizable" => set(&mut state.maximizable, payload(&data.cmd)?),
+            "setMinimizable" => set(&mut state.minimizable, payload(&data.cmd)?),
+            "setClosable" => set(&mut state.closable, payload(&data.cmd)?),
+            "setTitle" => set(&mut state.title, payload(&data.cmd)?),
+            "maximize" | "unmaximize" | "toggleMaximize" => {
+                state.maximized = match kind.as_str() {
+                    "maximize" => true,
+                    "unmaximize" => false,
+                    _ => !state.maximized,
+                };
+                resized(&label, state)
+            }
+            "minimize" => set(&mut state.minimized, true),
+            "unminimize" => set(&mut state.minimized, false),
+            "show" => set(&mut state.visible, true),
+            "hide" => set(&mut state.visible, false),
+            "setDecorations" => set(&mut state.decorations, payload(&data.cmd)?),
+            "setAlwaysOnTop" => set(&mut state.always_on_top, payload(&data.cmd)?),
+            "setFullscreen" => {
+                state.fullscreen = payload(&data.cmd)?;
+                resized(&label, state)
+            }
+            "setSize" => {
+                let size = match payload(&data.cmd)? {
+                    Dimension::Logical(LogicalSize { width, height }) => SizePayload {
+                        width: (width * state.scale_factor).round() as u32,
+                        height: (height * state.scale_factor).round() as u32,
+                    },
+                    Dimension::Physical(size) => size,
+                };
+
+                state.width = size.width;
+                state.height = size.height;
+                resized(&label, state)
+            }
+            "setPosition" => {
+                let position = match payload(&data.cmd)? {
+                    Dimension::Logical(LogicalPosition { x, y }) => PositionPayload {
+                        x: (x * state.scale_factor).round() as i32,
+                        y: (y * state.scale_factor).round() as i32,
+                    },
+                    Dimension::Physical(position) => position,
+                };
+
+                state.x = position.x;
+                state.y = position.y;
+                emit(&label, "tauri://move", &position)
+            }
+            "setFocus" => focus(&mut windows, &label),
+            "close" => {
+                windows.remove(&label);
+                set_registered(&label, false)?;
+                emit(&label, "tauri://destroyed", &())
+            }
+            // calls that don't change any tracked state
+            "center"
+            | "requestUserAttention"
+            | "setContentProtected"
+            | "setMinSize"
+            | "setMaxSize"
+            | "setIcon"
+            | "setSkipTaskbar"
+            | "setCursorGrab"
+            | "setCursorVisible"
+            | "setCursorIcon"
+            | "setCursorPosition"
+            | "setIgnoreCursorEvents"
+            | "startDragging" => Ok(JsValue::NULL),
+            _ => Err(JsValue::from_str(&format!(
+                "Unmocked command `Window.manage` of type `{}`",
+                kind
+            ))),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ManageMessage {
+    data: ManageData,
+}
+
+#[derive(Deserialize)]
+struct ManageData {
+    label: Option<String>,
+    #[serde(with = "serde_wasm_bindgen::preserve")]
+    cmd: JsValue,
+}
+
+#[derive(Deserialize)]
+struct CreateWebviewMessage {
+    data: CreateWebviewData,
+}
+
+#[derive(Deserialize)]
+struct CreateWebviewData {
+    options: WindowOptions,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WindowOptions {
+    label: String,
+    title: Option<String>,
+    width: Option<f64>,
+    height: Option<f64>,
+    x: Option<f64>,
+    y: Option<f64>,
+    focus: Option<bool>,
+    maximized: Option<bool>,
+    fullscreen: Option<bool>,
+    visible: Option<bool>,
+    decorations: Option<bool>,
+    resizable: Option<bool>,
+    maximizable: Option<bool>,
+    minimizable: Option<bool>,
+    closable: Option<bool>,
+    always_on_top: Option<bool>,
+    theme: Option<Theme>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SizePayload {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PositionPayload {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Deserialize)]
+struct LogicalSize {
+    width: f64,
+    height: f64,
+}
+
+#[derive(Deserialize)]
+struct LogicalPosition {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "data")]
+enum Dimension<L, P> {
+    Logical(L),
+    Physical(P),
+}
+
+fn payload<T: DeserializeOwned>(cmd: &JsValue) -> Result<T, JsValue> {
+    let payload = js_sys::Reflect::get(cmd, &"payload".into())?;
+
+    Ok(serde_wasm_bindgen::from_value(payload)?)
+}
+
+fn set<T>(field: &mut T, value: T) -> Result<JsValue, JsValue> {
+    *field = value;
+
+    Ok(JsValue::NULL)
+}
+
+/// Focuses the window, blurring the one that was focused before.
+fn focus(windows: &mut BTreeMap<String, WindowState>, label: &str) -> Result<JsValue, JsValue> {
+    for (other, state) in windows.iter_mut() {
+        if other != label && state.focused {
+            state.focused = false;
+            emit(other, "tauri://blur", &())?;
+        }
+    }
+
+    if let Some(state) = windows.get_mut(label) {
+        state.focused = true;
+    }
+    emit(label, "tauri://focus", &())
+}
+
+fn resized(label: &str, state: &WindowState) -> Result<JsValue, JsValue> {
+    let size = SizePayload {
+        width: state.width,
+        height: state.height,
+    };
+
+    emit(label, "tauri://resize", &size)
+}
+
+/// Adds or removes the window from the ones [`mock_windows`] registered, so [`WebviewWindow::get_by_label`](crate::window::WebviewWindow::get_by_label) sees it.
+fn set_registered(label: &str, registered: bool) -> Result<(), JsValue> {
+    let metadata = js_sys::Reflect::get(&js_sys::global(), &"__TAURI_METADATA__".into())?;
+    let windows: js_sys::Array =
+        js_sys::Reflect::get(&metadata, &"__windows".into())?.dyn_into()?;
+
+    let others = windows
+        .iter()
+        .filter(|window| js_sys::Reflect::get(window, &"label".into()).ok() != Some(label.into()));
+    let updated: js_sys::Array = others.collect();
+
+    if registered {
+        let window = js_sys::Object::new();
+        js_sys::Reflect::set(&window, &"label".into(), &label.into())?;
+        updated.push(&window);
+    }
+
+    js_sys::Reflect::set(&metadata, &"__windows".into(), &updated)?;
+
+    Ok(())
+}
+
+fn emit<T: Serialize>(label: &str, event: &str, payload: &T) -> Result<JsValue, JsValue> {
+    let payload = serialize_response(payload)?;
+    dispatch_deferred(event.to_string(), Some(label.to_string()), payload);
+
+    Ok(JsValue::NULL)
+}
@@ -0,0 +1,154 @@
+//! Bindings to [`tauri-plugin-log`](https://github.com/tauri-apps/plugins-workspace/tree/v1/plugins/log).
+//!
+//! The plugin must be registered with the Tauri builder in the backend:
+//! ```rust,ignore
+//! tauri::Builder::default()
+//!     .plugin(tauri_plugin_log::Builder::default().build())
+//! ```
+//!
+//! [`TauriLogger`] forwards records of the [`log`] crate to the backend,
+//! [`attach_console`] streams the records logged by the backend.
+
+use futures::{Stream, StreamExt};
+use log::{Metadata, Record};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u16)]
+enum LogLevel {
+    Trace = 1,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl From<log::Level> for LogLevel {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Trace => LogLevel::Trace,
+            log::Level::Debug => LogLevel::Debug,
+            log::Level::Info => LogLevel::Info,
+            log::Level::Warn => LogLevel::Warn,
+            log::Level::Error => LogLevel::Error,
+        }
+    }
+}
+
+impl From<LogLevel> for log::Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => log::Level::Trace,
+            LogLevel::Debug => log::Level::Debug,
+            LogLevel::Info => log::Level::Info,
+            LogLevel::Warn => log::Level::Warn,
+            LogLevel::Error => log::Level::Error,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct LogArgs {
+    level: LogLevel,
+    message: String,
+    location: String,
+    file: Option<String>,
+    line: Option<u32>,
+}
+
+/// A [`log::Log`] implementation that forwards records to the backend.
+///
+/// The record's target is passed as location, along with the file and line the record originates from.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use log::LevelFilter;
+/// use tauri_sys::plugin_log::TauriLogger;
+///
+/// static LOGGER: TauriLogger = TauriLogger;
+///
+/// fn main() {
+///     log::set_logger(&LOGGER)
+///         .map(|()| log::set_max_level(LevelFilter::Trace))
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TauriLogger;
+
+impl log::Log for TauriLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let args = LogArgs {
+                level: record.level().into(),
+                message: format!("{}", record.args()),
+                location: record.target().to_string(),
+                file: record.file().map(ToString::to_string),
+                line: record.line(),
+            };
+
+            wasm_bindgen_futures::spawn_local(async move {
+                // logging the failure would recurse into this logger
+                let _ = crate::tauri::plugin("log")
+                    .invoke::<_, ()>("log", &args)
+                    .await;
+            });
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// A record logged by the backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    /// The level the record was logged with.
+    pub level: log::Level,
+    /// The formatted message of the record.
+    pub message: String,
+}
+
+#[derive(Deserialize)]
+struct RecordPayload {
+    level: LogLevel,
+    message: String,
+}
+
+/// Listen to the records logged by the backend.
+///
+/// This is the equivalent of `attachConsole` in the JS API, leaving it up to the caller where to print the records.
+///
+/// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+/// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::plugin_log::attach_console;
+/// use web_sys::console;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut records = attach_console().await?;
+///
+/// while let Some(record) = records.next().await {
+///     // not `log::info!`, as a `TauriLogger` would send the record back to the backend
+///     console::log_1(&format!("[{}] {}", record.level, record.message).into());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn attach_console() -> crate::Result<impl Stream<Item = LogRecord>> {
+    let events = crate::event::listen::<RecordPayload>("log://log").await?;
+
+    Ok(events.map(|event| LogRecord {
+        level: event.payload.level.into(),
+        message: event.payload.message,
+    }))
+}
@@ -0,0 +1,536 @@
+//! Access the system shell. Allows you to spawn child processes.
+//!
+//! The APIs must be added to `tauri.allowlist.shell` in `tauri.conf.json`:
+//! ```json
+//! {
+//!     "tauri": {
+//!         "allowlist": {
+//!             "shell": {
+//!                 "all": true, // enable all shell APIs
+//!                 "execute": true, // enable process spawn APIs
+//!                 "sidecar": true, // enable spawning sidecars
+//!                 "open": true // enable opening files/URLs using the default program
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//! It is recommended to allowlist only the APIs you use for optimal bundle size and security.
+//!
+//! ## Security
+//!
+//! This API has a scope configuration that forces you to restrict the programs and arguments that can be used.
+//! See [`tauri.allowlist.shell.scope`](https://tauri.app/v1/api/config#shellallowlistconfig.scope) for details.
+
+use crate::Error;
+use futures::{channel::mpsc, Stream, StreamExt};
+use js_sys::Array;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+use url::Url;
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+
+/// Events emitted by a spawned child process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandEvent {
+    /// A line of the child's stdout.
+    Stdout(String),
+    /// A line of the child's stderr.
+    Stderr(String),
+    /// An error happened waiting for the command to finish or converting the stdout/stderr bytes to an UTF-8 string.
+    Error(String),
+    /// The child process terminated.
+    Terminated {
+        /// The exit code of the process. `None` if the process was terminated by a signal on Unix.
+        code: Option<i32>,
+        /// If the process was terminated by a signal, represents that signal.
+        signal: Option<i32>,
+    },
+}
+
+#[derive(Deserialize)]
+struct TerminatedPayload {
+    code: Option<i32>,
+    signal: Option<i32>,
+}
+
+/// The output of a finished process.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Output {
+    /// The exit code of the process. `None` if the process was terminated by a signal on Unix.
+    pub code: Option<i32>,
+    /// If the process was terminated by a signal, represents that signal.
+    pub signal: Option<i32>,
+    /// The data that the process wrote to stdout.
+    pub stdout: String,
+    /// The data that the process wrote to stderr.
+    pub stderr: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CommandOptions<'a> {
+    sidecar: bool,
+    cwd: Option<&'a Path>,
+    env: Option<HashMap<&'a str, &'a str>>,
+    encoding: Option<&'a str>,
+}
+
+/// The entry point for spawning child processes.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::shell::Command;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let output = Command::new("echo")
+///     .add_arg("message")
+///     .output()
+///     .await?;
+///
+/// assert_eq!(output.code, Some(0));
+/// assert_eq!(output.stdout, "message");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Command<'a> {
+    program: &'a str,
+    args: Vec<&'a str>,
+    options: CommandOptions<'a>,
+}
+
+impl<'a> Command<'a> {
+    /// Creates a new command for launching the given program.
+    ///
+    /// The program must be configured on `tauri.conf.json > tauri > allowlist > shell > scope`.
+    pub fn new(program: &'a str) -> Self {
+        Self {
+            program,
+            args: Vec::new(),
+            options: CommandOptions::default(),
+        }
+    }
+
+    /// Creates a new command for launching the given sidecar program.
+    ///
+    /// The program name must match one of the entries of `tauri.conf.json > tauri > bundle > externalBin` exactly,
+    /// e.g. `binaries/my-sidecar`, without the target triple suffix and file extension Tauri appends to the binary.
+    /// Empty names, absolute paths, `\` separators, `:` and `.` or `..` components are rejected.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use serde::Deserialize;
+    /// use tauri_sys::shell::{Command, CommandEvent};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Progress {
+    ///     done: u32,
+    ///     total: u32,
+    /// }
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let command = Command::sidecar("binaries/my-sidecar")?;
+    /// let (events, _child) = command.spawn().await?;
+    ///
+    /// // the sidecar writes one JSON object per line to stdout
+    /// let mut progress = events.filter_map(|event| async move {
+    ///     match event {
+    ///         CommandEvent::Stdout(line) => {
+    ///             let raw = js_sys::JSON::parse(&line).ok()?;
+    ///             serde_wasm_bindgen::from_value::<Progress>(raw).ok()
+    ///         }
+    ///         _ => None,
+    ///     }
+    /// });
+    ///
+    /// while let Some(Progress { done, total }) = progress.next().await {
+    ///     log::info!("{}/{}", done, total);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Requires [`allowlist > shell > sidecar`](https://tauri.app/v1/api/config#shellallowlistconfig.sidecar) to be enabled.
+    pub fn sidecar(program: &'a str) -> crate::Result<Self> {
+        validate_sidecar(program)?;
+
+        let mut command = Self::new(program);
+        command.options.sidecar = true;
+
+        Ok(command)
+    }
+
+    /// Appends an argument to the command.
+    pub fn add_arg(&mut self, arg: &'a str) -> &mut Self {
+        self.args.push(arg);
+        self
+    }
+
+    /// Appends many arguments to the command.
+    pub fn add_args(&mut self, args: impl IntoIterator<Item = &'a str>) -> &mut Self {
+        self.args.extend(args);
+        self
+    }
+
+    /// Sets the working directory for the child process.
+    pub fn set_cwd(&mut self, cwd: &'a Path) -> &mut Self {
+        self.options.cwd = Some(cwd);
+        self
+    }
+
+    /// Adds an environment variable to the child process.
+    pub fn add_env(&mut self, key: &'a str, value: &'a str) -> &mut Self {
+        self.options
+            .env
+            .get_or_insert_with(HashMap::new)
+            .insert(key, value);
+        self
+    }
+
+    /// Adds many environment variables to the child process.
+    pub fn add_envs(&mut self, envs: impl IntoIterator<Item = (&'a str, &'a str)>) -> &mut Self {
+        self.options
+            .env
+            .get_or_insert_with(HashMap::new)
+            .extend(envs);
+        self
+    }
+
+    /// Character encoding for stdout/stderr.
+    pub fn set_encoding(&mut self, encoding: &'a str) -> &mut Self {
+        self.options.encoding = Some(encoding);
+        self
+    }
+
+    /// Whether the program is a sidecar binary bundled with the application.
+    ///
+    /// Prefer [`Command::sidecar`], which validates the program name upfront.
+    pub fn set_sidecar(&mut self, sidecar: bool) -> &mut Self {
+        self.options.sidecar = sidecar;
+        self
+    }
+
+    /// Executes the command as a child process, waiting for it to finish and collecting all of its output.
+    ///
+    /// Requires [`allowlist > shell > execute`](https://tauri.app/v1/api/config#shellallowlistconfig.execute) to be enabled.
+    pub async fn output(&self) -> crate::Result<Output> {
+        let raw = self.to_js()?.execute().await?;
+
+        Ok(serde_wasm_bindgen::from_value(raw)?)
+    }
+
+    /// Executes the command as a child process, returning a stream of its events and a handle to the child.
+    ///
+    /// The returned Stream will automatically remove its event handlers when dropped, so no manual cleanup is needed.
+    /// Dropping the stream does not kill the child process, use [`Child::kill`] for that.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tauri_sys::shell::{Command, CommandEvent};
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let command = Command::new("node");
+    /// let (mut events, child) = command.spawn().await?;
+    ///
+    /// child.write_str("console.log('Hello Tauri')\n").await?;
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         CommandEvent::Stdout(line) => log::info!("{}", line),
+    ///         CommandEvent::Terminated { code, .. } => log::info!("exited with {:?}", code),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Requires [`allowlist > shell > execute`](https://tauri.app/v1/api/config#shellallowlistconfig.execute) to be enabled.
+    pub async fn spawn(&self) -> crate::Result<(impl Stream<Item = CommandEvent>, Child)> {
+        let command = self.to_js()?;
+        let (tx, rx) = mpsc::unbounded::<CommandEvent>();
+
+        let on_stdout = {
+            let tx = tx.clone();
+            Closure::<dyn FnMut(JsValue)>::new(move |raw: JsValue| {
+                let _ =
+                    tx.unbounded_send(CommandEvent::Stdout(raw.as_string().unwrap_or_default()));
+            })
+        };
+        let on_stderr = {
+            let tx = tx.clone();
+            Closure::<dyn FnMut(JsValue)>::new(move |raw: JsValue| {
+                let _ =
+                    tx.unbounded_send(CommandEvent::Stderr(raw.as_string().unwrap_or_default()));
+            })
+        };
+        let on_error = {
+            let tx = tx.clone();
+            Closure::<dyn FnMut(JsValue)>::new(move |raw: JsValue| {
+                let msg = raw.as_string().unwrap_or_else(|| format!("{:?}", raw));
+                let _ = tx.unbounded_send(CommandEvent::Error(msg));
+            })
+        };
+        let on_close = Closure::<dyn FnMut(JsValue)>::new(move |raw| {
+            let event = match serde_wasm_bindgen::from_value::<TerminatedPayload>(raw) {
+                Ok(TerminatedPayload { code, signal }) => CommandEvent::Terminated { code, signal },
+                Err(err) => CommandEvent::Error(err.to_string()),
+            };
+            let _ = tx.unbounded_send(event);
+            // the child is gone, so no more events will follow
+            tx.close_channel();
+        });
+
+        command.stdout().on("data", &on_stdout);
+        command.stderr().on("data", &on_stderr);
+        command.on("error", &on_error);
+        command.on("close", &on_close);
+
+        let events = Events {
+            rx,
+            command: command.clone(),
+            _handlers: [on_stdout, on_stderr, on_error, on_close],
+        };
+
+        let child = command.spawn().await?;
+
+        Ok((events, Child(child.unchecked_into())))
+    }
+
+    fn to_js(&self) -> crate::Result<inner::Command> {
+        if self.options.sidecar {
+            validate_sidecar(self.program)?;
+        }
+
+        let args = Array::from_iter(self.args.iter().map(|arg| JsValue::from_str(arg)));
+        let options = self
+            .options
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())?;
+
+        Ok(inner::Command::new(self.program, args, options))
+    }
+}
+
+fn validate_sidecar(program: &str) -> crate::Result<()> {
+    let is_valid = !program.contains(['\\', ':'])
+        && program
+            .split('/')
+            .all(|component| !matches!(component, "" | "." | ".."));
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidSidecar(program.to_string()))
+    }
+}
+
+struct Events {
+    rx: mpsc::UnboundedReceiver<CommandEvent>,
+    command: inner::Command,
+    _handlers: [Closure<dyn FnMut(JsValue)>; 4],
+}
+
+impl Drop for Events {
+    fn drop(&mut self) {
+        log::debug!("Removing command event handlers");
+        self.command.stdout().removeAllListeners();
+        self.command.stderr().removeAllListeners();
+        self.command.removeAllListeners();
+    }
+}
+
+impl Stream for Events {
+    type Item = CommandEvent;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
+/// A handle to a spawned child process.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Child(inner::Child);
+
+impl Child {
+    /// The child process `pid`.
+    pub fn pid(&self) -> u32 {
+        self.0.pid()
+    }
+
+    /// Writes raw bytes to the stdin of the child process.
+    pub async fn write(&self, data: &[u8]) -> crate::Result<()> {
+        Ok(self.0.write(js_sys::Uint8Array::from(data).into()).await?)
+    }
+
+    /// Writes a string to the stdin of the child process.
+    pub async fn write_str(&self, data: &str) -> crate::Result<()> {
+        Ok(self.0.write(JsValue::from_str(data)).await?)
+    }
+
+    /// Kills the child process.
+    pub async fn kill(&self) -> crate::Result<()> {
+        Ok(self.0.kill().await?)
+    }
+}
+
+/// A target that can be opened with [`open`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenTarget {
+    Url(Url),
+    Path(PathBuf),
+}
+
+impl From<Url> for OpenTarget {
+    fn from(url: Url) -> Self {
+        OpenTarget::Url(url)
+    }
+}
+
+impl From<PathBuf> for OpenTarget {
+    fn from(path: PathBuf) -> Self {
+        OpenTarget::Path(path)
+    }
+}
+
+impl From<&Path> for OpenTarget {
+    fn from(path: &Path) -> Self {
+        OpenTarget::Path(path.to_path_buf())
+    }
+}
+
+/// The programs a target can be opened with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OpenWith {
+    Firefox,
+    GoogleChrome,
+    Chromium,
+    Safari,
+    Open,
+    Start,
+    XdgOpen,
+    Gio,
+    GnomeOpen,
+    KdeOpen,
+    WslView,
+    /// Any other program, it must be allowed by the shell `open` scope.
+    Other(String),
+}
+
+impl Display for OpenWith {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OpenWith::Firefox => write!(f, "firefox"),
+            OpenWith::GoogleChrome => write!(f, "google chrome"),
+            OpenWith::Chromium => write!(f, "chromium"),
+            OpenWith::Safari => write!(f, "safari"),
+            OpenWith::Open => write!(f, "open"),
+            OpenWith::Start => write!(f, "start"),
+            OpenWith::XdgOpen => write!(f, "xdg-open"),
+            OpenWith::Gio => write!(f, "gio"),
+            OpenWith::GnomeOpen => write!(f, "gnome-open"),
+            OpenWith::KdeOpen => write!(f, "kde-open"),
+            OpenWith::WslView => write!(f, "wslview"),
+            OpenWith::Other(program) => write!(f, "{}", program),
+        }
+    }
+}
+
+/// Opens a path or URL with the system's default app, or the one specified with `with`.
+///
+/// The target must match the regex configured on `tauri.conf.json > tauri > allowlist > shell > open`,
+/// which defaults to `^((mailto:\w+)|(tel:\w+)|(https?://\w+)).+`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tauri_sys::shell::{open, OpenWith};
+/// use url::Url;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // opens the given URL on the default browser:
+/// open(Url::parse("https://github.com/tauri-apps/tauri")?, None).await?;
+/// // opens the given URL using `firefox`:
+/// open(Url::parse("https://github.com/tauri-apps/tauri")?, Some(OpenWith::Firefox)).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Requires [`allowlist > shell > open`](https://tauri.app/v1/api/config#shellallowlistconfig.open) to be enabled.
+pub async fn open(target: impl Into<OpenTarget>, with: Option<OpenWith>) -> crate::Result<()> {
+    let target = match target.into() {
+        OpenTarget::Url(url) => url.to_string(),
+        OpenTarget::Path(path) => match path.to_str() {
+            Some(path) => path.to_string(),
+            None => return Err(Error::Utf8(path)),
+        },
+    };
+
+    Ok(inner::open(&target, with.map(|with| with.to_string()).as_deref()).await?)
+}
+
+mod inner {
+    use js_sys::Array;
+    use wasm_bindgen::{
+        prelude::{wasm_bindgen, Closure},
+        JsValue,
+    };
+
+    #[wasm_bindgen(module = "/src/shell.js")]
+    extern "C" {
+        #[derive(Debug, Clone, PartialEq)]
+        pub type EventEmitter;
+        #[wasm_bindgen(method)]
+        pub fn on(this: &EventEmitter, eventName: &str, listener: &Closure<dyn FnMut(JsValue)>);
+        #[wasm_bindgen(method)]
+        pub fn removeAllListeners(this: &EventEmitter);
+    }
+
+    #[wasm_bindgen(module = "/src/shell.js")]
+    extern "C" {
+        #[wasm_bindgen(extends = EventEmitter)]
+        #[derive(Debug, Clone, PartialEq)]
+        pub type Command;
+        #[wasm_bindgen(constructor)]
+        pub fn new(program: &str, args: Array, options: JsValue) -> Command;
+        #[wasm_bindgen(method, getter)]
+        pub fn stdout(this: &Command) -> EventEmitter;
+        #[wasm_bindgen(method, getter)]
+        pub fn stderr(this: &Command) -> EventEmitter;
+        #[wasm_bindgen(method, catch)]
+        pub async fn spawn(this: &Command) -> Result<JsValue, JsValue>;
+        #[wasm_bindgen(method, catch)]
+        pub async fn execute(this: &Command) -> Result<JsValue, JsValue>;
+    }
+
+    #[wasm_bindgen(module = "/src/shell.js")]
+    extern "C" {
+        #[derive(Debug, Clone, PartialEq)]
+        pub type Child;
+        #[wasm_bindgen(method, getter)]
+        pub fn pid(this: &Child) -> u32;
+        #[wasm_bindgen(method, catch)]
+        pub async fn write(this: &Child, data: JsValue) -> Result<(), JsValue>;
+        #[wasm_bindgen(method, catch)]
+        pub async fn kill(this: &Child) -> Result<(), JsValue>;
+    }
+
+    #[wasm_bindgen(module = "/src/shell.js")]
+    extern "C" {
+        #[wasm_bindgen(catch)]
+        pub async fn open(path: &str, openWith: Option<&str>) -> Result<(), JsValue>;
+    }
+}
@@ -1,6 +1,7 @@
 //! Invoke your custom commands.
 
 use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::{Debug, Display};
 use url::Url;
 
 /// Convert a device file path to an URL that can be loaded by the webview.
@@ -70,6 +71,313 @@ pub async fn invoke<A: Serialize, R: DeserializeOwned>(cmd: &str, args: &A) -> c
     serde_wasm_bindgen::from_value(raw).map_err(Into::into)
 }
 
+/// A statically known backend command, tying the command name to the types of its arguments, output and error.
+///
+/// Use the [`command!`](crate::command) macro to define commands and [`call`] to invoke them.
+pub trait Command {
+    /// The command name.
+    const NAME: &'static str;
+    /// The arguments passed to the command.
+    type Args: Serialize;
+    /// The value the command resolves to.
+    type Output: DeserializeOwned;
+    /// The error the command rejects with.
+    type Error: DeserializeOwned;
+}
+
+/// The error returned by [`call`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvokeError<E> {
+    /// The command itself returned an error.
+    Command(E),
+    /// The command couldn't be invoked or its response couldn't be deserialized.
+    Invoke(crate::Error),
+}
+
+impl<E> From<crate::Error> for InvokeError<E> {
+    fn from(err: crate::Error) -> Self {
+        Self::Invoke(err)
+    }
+}
+
+impl<E: Display> Display for InvokeError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvokeError::Command(err) => write!(f, "Command returned Error: {}", err),
+            InvokeError::Invoke(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for InvokeError<E> {}
+
+/// Invokes a [`Command`] on the backend.
+///
+/// See [`invoke_with_error`] for how rejections are mapped to [`InvokeError`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::Deserialize;
+/// use tauri_sys::tauri::call;
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// tauri_sys::command! {
+///     /// Logs the user in.
+///     pub struct Login("login") -> Result<User, String> {
+///         user_name: String,
+///         password: String,
+///     }
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let user = call::<Login>(&Login {
+///     user_name: "tauri".to_string(),
+///     password: "poiwe3h4r5ip3yrhtew9ty".to_string(),
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn call<C: Command>(args: &C::Args) -> Result<C::Output, InvokeError<C::Error>> {
+    invoke_with_error(C::NAME, args).await
+}
+
+/// Sends a message to the backend, recovering the error type the command rejects with.
+///
+/// Rejections that deserialize into `E` are returned as [`InvokeError::Command`].
+/// Allowlist and scope rejections, as well as rejections that don't match `E`, are returned as [`InvokeError::Invoke`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::{Deserialize, Serialize};
+/// use tauri_sys::tauri::{invoke_with_error, InvokeError};
+///
+/// #[derive(Serialize)]
+/// struct User<'a> {
+///     user: &'a str,
+///     password: &'a str,
+/// }
+///
+/// #[derive(Debug, Deserialize)]
+/// enum LoginError {
+///     UnknownUser,
+///     WrongPassword,
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let res = invoke_with_error::<_, (), LoginError>(
+///     "login",
+///     &User { user: "tauri", password: "poiwe3h4r5ip3yrhtew9ty" },
+/// )
+/// .await;
+///
+/// match res {
+///     Ok(()) => log::info!("Logged in"),
+///     Err(InvokeError::Command(err)) => log::warn!("Login failed: {:?}", err),
+///     Err(InvokeError::Invoke(err)) => return Err(err.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn invoke_with_error<A, R, E>(cmd: &str, args: &A) -> Result<R, InvokeError<E>>
+where
+    A: Serialize,
+    R: DeserializeOwned,
+    E: DeserializeOwned,
+{
+    let args = serde_wasm_bindgen::to_value(args).map_err(crate::Error::from)?;
+
+    match inner::invoke(cmd, args).await {
+        Ok(raw) => Ok(serde_wasm_bindgen::from_value(raw).map_err(crate::Error::from)?),
+        Err(raw) => {
+            let err = crate::Error::from(raw);
+
+            match err.decode() {
+                Some(err) => Err(InvokeError::Command(err)),
+                None => Err(InvokeError::Invoke(err)),
+            }
+        }
+    }
+}
+
+/// Defines one or more [`Command`](crate::tauri::Command)s.
+///
+/// Each command becomes a struct holding its arguments. Field names are renamed to camelCase, matching the renaming Tauri applies to `#[tauri::command]` arguments.
+/// The structs implement `Serialize` through this crate, so the calling crate doesn't need to depend on `serde` itself.
+/// Fields take doc comments followed by `#[serde(...)]` attributes, which customize how the field is serialized.
+/// Commands declared as `-> Result<T, E>` reject with `E`, all others reject with a `String`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// pub enum ConfigError {
+///     NotFound,
+///     Invalid(String),
+/// }
+///
+/// tauri_sys::command! {
+///     /// Greets the given person.
+///     pub struct Greet("greet") -> String {
+///         /// The name to greet.
+///         #[serde(rename = "name")]
+///         first_name: String,
+///     }
+///
+///     pub struct ReadConfig("read_config") -> Result<Vec<u8>, ConfigError> {}
+/// }
+/// ```
+#[macro_export]
+macro_rules! command {
+    () => {};
+    (@define
+        $(#[$meta:meta])* $vis:vis $name:ident($cmd:literal) $output:ty, $error:ty,
+        { $([$(#[$field_doc:meta])*] [$(#[$field_serde:meta])*] $field_vis:vis $field:ident: $field_ty:ty,)* }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        $vis struct $name {
+            $($(#[$field_doc])* $field_vis $field: $field_ty,)*
+        }
+
+        const _: () = {
+            // `#[serde(crate = "...")]` is parsed from a string, so it can't refer to `$crate` directly
+            use $crate::__private::__tauri_sys_serde;
+
+            type Remote = $name;
+
+            #[allow(dead_code)]
+            #[derive($crate::__private::__tauri_sys_serde::Serialize)]
+            #[serde(crate = "__tauri_sys_serde", remote = "Remote", rename_all = "camelCase")]
+            struct Args {
+                $($(#[$field_serde])* $field: $field_ty,)*
+            }
+
+            impl $crate::__private::__tauri_sys_serde::Serialize for $name {
+                fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+                where
+                    S: $crate::__private::__tauri_sys_serde::Serializer,
+                {
+                    Args::serialize(self, serializer)
+                }
+            }
+        };
+
+        impl $crate::tauri::Command for $name {
+            const NAME: &'static str = $cmd;
+            type Args = Self;
+            type Output = $output;
+            type Error = $error;
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident($cmd:literal) -> Result<$output:ty, $error:ty> {
+            $(
+                $(#[doc = $field_doc:literal])*
+                $(#[serde($($field_serde:tt)*)])*
+                $field_vis:vis $field:ident: $field_ty:ty
+            ),* $(,)?
+        }
+        $($rest:tt)*
+    ) => {
+        $crate::command!(@define
+            $(#[$meta])* $vis $name($cmd) $output, $error,
+            { $([$(#[doc = $field_doc])*] [$(#[serde($($field_serde)*)])*] $field_vis $field: $field_ty,)* }
+        );
+        $crate::command!($($rest)*);
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident($cmd:literal) -> $output:ty {
+            $(
+                $(#[doc = $field_doc:literal])*
+                $(#[serde($($field_serde:tt)*)])*
+                $field_vis:vis $field:ident: $field_ty:ty
+            ),* $(,)?
+        }
+        $($rest:tt)*
+    ) => {
+        $crate::command!(@define
+            $(#[$meta])* $vis $name($cmd) $output, ::std::string::String,
+            { $([$(#[doc = $field_doc])*] [$(#[serde($($field_serde)*)])*] $field_vis $field: $field_ty,)* }
+        );
+        $crate::command!($($rest)*);
+    };
+}
+
+/// A handle to the commands of a Tauri plugin, see [`plugin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Plugin<'a> {
+    name: &'a str,
+}
+
+impl<'a> Plugin<'a> {
+    /// The name of the plugin.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Sends a message to a command of the plugin.
+    ///
+    /// See [`invoke`] for details.
+    pub async fn invoke<A: Serialize, R: DeserializeOwned>(
+        &self,
+        cmd: &str,
+        args: &A,
+    ) -> crate::Result<R> {
+        invoke(&self.command(cmd), args).await
+    }
+
+    /// Sends a message to a command of the plugin, recovering the error type the command rejects with.
+    ///
+    /// See [`invoke_with_error`] for details.
+    pub async fn invoke_with_error<A, R, E>(&self, cmd: &str, args: &A) -> Result<R, InvokeError<E>>
+    where
+        A: Serialize,
+        R: DeserializeOwned,
+        E: DeserializeOwned,
+    {
+        invoke_with_error(&self.command(cmd), args).await
+    }
+
+    fn command(&self, cmd: &str) -> String {
+        format!("plugin:{}|{}", self.name, cmd)
+    }
+}
+
+/// Returns a handle to the commands of the plugin with the given name.
+///
+/// Plugin commands are addressed as `plugin:<name>|<cmd>`, which the handle takes care of.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use serde::Serialize;
+/// use tauri_sys::tauri::plugin;
+///
+/// #[derive(Serialize)]
+/// struct Args<'a> {
+///     key: &'a str,
+/// }
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let value: Option<String> = plugin("store").invoke("get", &Args { key: "theme" }).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn plugin(name: &str) -> Plugin<'_> {
+    Plugin { name }
+}
+
 /// Transforms a callback function to a string identifier that can be passed to the backend.
 ///
 /// The backend uses the identifier to `eval()` the callback.
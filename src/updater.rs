@@ -1,9 +1,9 @@
 //! Customize the auto updater flow.
 
-use futures::{Stream, channel::mpsc};
+use futures::Stream;
 use serde::Deserialize;
 use wasm_bindgen::{prelude::Closure, JsValue};
-use crate::event::Listen;
+use crate::{channel, event::Listen};
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct UpdateManifest {
@@ -25,7 +25,7 @@ struct UpdateStatusResult {
     status: UpdateStatus,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum UpdateStatus {
     #[serde(rename = "PENDING")]
     Pending,
@@ -80,45 +80,54 @@ pub async fn install_update() -> crate::Result<()> {
 
 /// Listen to an updater event.
 ///
+/// Errors reported by the updater are yielded as [`Error::Updater`](crate::Error::Updater),
+/// events that can't be deserialized as [`Error::InvalidPayload`](crate::Error::InvalidPayload).
+///
 /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
 /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
 /// 
 /// # Example
 ///
 /// ```rust,no_run
+/// use futures::StreamExt;
 /// use tauri_sys::updater::updater_events;
-/// use web_sys::console;
 ///
 /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
-/// let events = updater_events();
+/// let mut events = updater_events().await?;
 ///
 /// while let Some(event) = events.next().await {
-///     console::log_1(&format!("Updater event {:?}", event).into());
+///     match event {
+///         Ok(status) => log::info!("Updater event {:?}", status),
+///         Err(err) => log::error!("Updater failed: {}", err),
+///     }
 /// }
 /// # Ok(())
 /// # }
 /// ```
 #[inline(always)]
-pub async fn updater_events() -> crate::Result<impl Stream<Item = Result<UpdateStatus, String>>> {
-    let (tx, rx) = mpsc::unbounded::<Result<UpdateStatus, String>>();
-
-    let closure = Closure::<dyn FnMut(JsValue)>::new(move |raw| {
-        let raw: UpdateStatusResult = serde_wasm_bindgen::from_value(raw).unwrap();
+pub async fn updater_events() -> crate::Result<impl Stream<Item = crate::Result<UpdateStatus>>> {
+    let (tx, rx) = channel::channel(Default::default());
 
-        let msg = if let Some(error) = raw.error {
-            Err(error)
-        } else {
-            Ok(raw.status)
+    let closure = Closure::<dyn FnMut(JsValue)>::new(move |raw: JsValue| {
+        let msg = match serde_wasm_bindgen::from_value::<UpdateStatusResult>(raw.clone()) {
+            Ok(UpdateStatusResult {
+                error: Some(error), ..
+            }) => Err(crate::Error::Updater(error)),
+            Ok(UpdateStatusResult { status, .. }) => Ok(status),
+            Err(err) => Err(crate::Error::InvalidPayload {
+                message: err.to_string(),
+                raw: crate::error::to_json(&raw),
+            }),
         };
 
-        let _ = tx.unbounded_send(msg);
+        tx.send(msg);
     });
     let unlisten = inner::onUpdaterEvent(&closure).await?;
-    closure.forget();
 
     Ok(Listen {
         rx,
         unlisten: js_sys::Function::from(unlisten),
+        handler: Some(closure),
     })
 }
 
@@ -44,18 +44,25 @@
 //! It is recommended to allowlist only the APIs you use for optimal bundle size and security.
 
 use crate::{
-    event::{Event, Listen, Once},
+    channel,
+    event::{
+        self, current_window_label, deserialize_event, skip_invalid, Event, Listen, Once,
+        StreamConfig, TauriEvent,
+    },
     utils::ArrayIterator,
 };
-use futures::{
-    channel::{mpsc, oneshot},
-    Stream,
-};
+use futures::{channel::oneshot, future, stream, Stream, StreamExt};
 use js_sys::Array;
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::fmt::Display;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize, Serialize,
+};
+use std::{fmt::Display, path::PathBuf, rc::Rc};
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
 
+/// Maps the raw event of one of the listeners of [`WebviewWindow::listen_many`].
+type MapEvent<T> = fn(JsValue) -> crate::Result<T>;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Theme {
     #[serde(rename = "light")]
@@ -75,6 +82,102 @@ pub enum TitleBarStyle {
     Overlay,
 }
 
+/// Describes a change of the scale factor of a window, e.g. when it's moved to a different monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleFactorChanged {
+    /// The new scale factor.
+    pub scale_factor: f64,
+    /// The new inner size of the window.
+    pub size: PhysicalSize,
+}
+
+/// A request to close a window, yielded by [`WebviewWindow::on_close_requested`].
+///
+/// The window is closed once the event is dropped, unless [`CloseRequestedEvent::prevent_default`] was called.
+/// Since the decision is only made on drop, the event can be held across `.await` points, e.g. to ask the user for confirmation first.
+#[derive(Debug)]
+pub struct CloseRequestedEvent {
+    window: WebviewWindow,
+    id: f32,
+    prevented: bool,
+}
+
+impl CloseRequestedEvent {
+    /// The window that is requested to close.
+    pub fn window(&self) -> &WebviewWindow {
+        &self.window
+    }
+
+    /// Event identifier used to unlisten.
+    pub fn id(&self) -> f32 {
+        self.id
+    }
+
+    /// Prevents the window from being closed.
+    pub fn prevent_default(&mut self) {
+        self.prevented = true;
+    }
+
+    /// Whether [`CloseRequestedEvent::prevent_default`] was called.
+    pub fn is_default_prevented(&self) -> bool {
+        self.prevented
+    }
+}
+
+impl Drop for CloseRequestedEvent {
+    fn drop(&mut self) {
+        if self.prevented {
+            return;
+        }
+
+        let window = self.window.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Err(err) = window.close().await {
+                log::error!("failed to close window {}: {}", window.label(), err);
+            }
+        });
+    }
+}
+
+/// A menu item click, yielded by [`menu_events`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuEvent {
+    /// The label of the window the menu belongs to.
+    pub window_label: String,
+    /// The id of the clicked menu item.
+    pub menu_item_id: String,
+}
+
+/// A file drag and drop event, yielded by [`WebviewWindow::on_file_drop`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDropEvent {
+    /// Files are being dragged over the window.
+    Hovered(Vec<PathBuf>),
+    /// Files were dropped onto the window.
+    Dropped(Vec<PathBuf>),
+    /// The drag operation was cancelled.
+    Cancelled,
+}
+
+#[derive(Deserialize)]
+struct SizePayload {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Deserialize)]
+struct PositionPayload {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScaleFactorChangedPayload {
+    scale_factor: f64,
+    size: SizePayload,
+}
+
 /// Attention type to request on a window.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum UserAttentionType {
@@ -783,7 +886,7 @@ impl WebviewWindow {
     #[inline(always)]
     pub async fn emit<T: Serialize>(&self, event: &str, payload: &T) -> crate::Result<()> {
         self.0
-            .emit(event, serde_wasm_bindgen::to_value(payload).unwrap())
+            .emit(event, serde_wasm_bindgen::to_value(payload)?)
             .await?;
 
         Ok(())
@@ -791,6 +894,8 @@ impl WebviewWindow {
 
     /// Listen to an event emitted by the backend that is tied to the webview window.
     ///
+    /// Events whose payload can't be deserialized into `T` are logged and skipped, use [`WebviewWindow::listen_fallible`] to handle them yourself.
+    ///
     /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
     /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
     #[inline(always)]
@@ -798,22 +903,100 @@ impl WebviewWindow {
     where
         T: DeserializeOwned + 'static,
     {
-        let (tx, rx) = mpsc::unbounded::<Event<T>>();
+        self.listen_with(event, StreamConfig::default()).await
+    }
+
+    /// Listen to an event emitted by the backend that is tied to the webview window, buffering events as configured.
+    ///
+    /// See [`WebviewWindow::listen`] for details.
+    #[inline(always)]
+    pub async fn listen_with<T>(
+        &self,
+        event: &str,
+        config: StreamConfig,
+    ) -> crate::Result<impl Stream<Item = Event<T>>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let events = self.listen_owned(event, config).await?;
+
+        Ok(skip_invalid(events))
+    }
+
+    /// Listen to an event emitted by the backend that is tied to the webview window, yielding an error for every event whose payload can't be deserialized into `T`.
+    ///
+    /// The error is an [`Error::InvalidPayload`](crate::Error::InvalidPayload) that carries the raw payload, so it can be logged or inspected.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    #[inline(always)]
+    pub async fn listen_fallible<T>(
+        &self,
+        event: &str,
+    ) -> crate::Result<impl Stream<Item = crate::Result<Event<T>>>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        self.listen_owned(event, StreamConfig::default()).await
+    }
+
+    /// Like [`WebviewWindow::listen_fallible`], but the returned stream doesn't borrow the window.
+    async fn listen_owned<T>(
+        &self,
+        event: &str,
+        config: StreamConfig,
+    ) -> crate::Result<Listen<crate::Result<Event<T>>>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let (tx, rx) = channel::channel(config);
 
         let closure = Closure::<dyn FnMut(JsValue)>::new(move |raw| {
-            let _ = tx.unbounded_send(serde_wasm_bindgen::from_value(raw).unwrap());
+            tx.send(deserialize_event(raw));
         });
         let unlisten = self.0.listen(event, &closure).await?;
-        closure.forget();
 
         Ok(Listen {
             rx,
             unlisten: js_sys::Function::from(unlisten),
+            handler: Some(closure),
         })
     }
 
+    /// Listen to several events emitted by the backend that are tied to the webview window, yielding them in the order they arrive.
+    ///
+    /// Each event is mapped by the function it is paired with, events it fails for are logged and skipped.
+    async fn listen_many<T: 'static>(
+        &self,
+        events: &[(&str, MapEvent<T>)],
+    ) -> crate::Result<ListenMany<T>> {
+        let (tx, rx) = channel::channel(StreamConfig::default());
+        let tx = Rc::new(tx);
+        let mut listen = ListenMany {
+            rx,
+            listeners: Vec::with_capacity(events.len()),
+        };
+
+        for &(event, map) in events {
+            let tx = tx.clone();
+            let closure = Closure::<dyn FnMut(JsValue)>::new(move |raw| match map(raw) {
+                Ok(item) => tx.send(item),
+                Err(err) => log::error!("Skipping event: {}", err),
+            });
+            let unlisten = self.0.listen(event, &closure).await?;
+
+            listen
+                .listeners
+                .push((js_sys::Function::from(unlisten), Some(closure)));
+        }
+
+        Ok(listen)
+    }
+
     /// Listen to an one-off event emitted by the backend that is tied to the webview window.
     ///
+    /// Fails with [`Error::InvalidPayload`](crate::Error::InvalidPayload) if the payload can't be deserialized into `T`.
+    ///
     /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
     /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
     #[inline(always)]
@@ -821,21 +1004,256 @@ impl WebviewWindow {
     where
         T: DeserializeOwned + 'static,
     {
-        let (tx, rx) = oneshot::channel::<Event<T>>();
+        let (tx, rx) = oneshot::channel::<crate::Result<Event<T>>>();
 
         let closure: Closure<dyn FnMut(JsValue)> = Closure::once(move |raw| {
-            let _ = tx.send(serde_wasm_bindgen::from_value(raw).unwrap());
+            let _ = tx.send(deserialize_event(raw));
         });
         let unlisten = self.0.once(event, &closure).await?;
-        closure.forget();
 
         let fut = Once {
             rx,
             unlisten: js_sys::Function::from(unlisten),
+            handler: Some(closure),
         };
 
         fut.await
     }
+
+    /// Emits a [`TauriEvent`] to the backend, tied to the webview window.
+    #[inline(always)]
+    pub async fn emit_typed<E: TauriEvent>(&self, payload: &E::Payload) -> crate::Result<()> {
+        self.emit(E::NAME, payload).await
+    }
+
+    /// Listen to a [`TauriEvent`] emitted by the backend that is tied to the webview window.
+    ///
+    /// See [`WebviewWindow::listen`] for details.
+    #[inline(always)]
+    pub async fn listen_typed<E: TauriEvent>(
+        &self,
+    ) -> crate::Result<impl Stream<Item = Event<E::Payload>>> {
+        self.listen(E::NAME).await
+    }
+
+    /// Listen to an one-off [`TauriEvent`] emitted by the backend that is tied to the webview window.
+    ///
+    /// See [`WebviewWindow::once`] for details.
+    #[inline(always)]
+    pub async fn once_typed<E: TauriEvent>(&self) -> crate::Result<Event<E::Payload>> {
+        self.once(E::NAME).await
+    }
+
+    /// Listen to window resize.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tauri_sys::window::current_window;
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let win = current_window();
+    /// let mut sizes = win.on_resized().await?;
+    ///
+    /// while let Some(size) = sizes.next().await {
+    ///     log::debug!("Window resized to {}x{}", size.width(), size.height());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn on_resized(&self) -> crate::Result<impl Stream<Item = PhysicalSize>> {
+        self.on_resized_with(StreamConfig::default()).await
+    }
+
+    /// Listen to window resize, buffering events as configured.
+    ///
+    /// See [`WebviewWindow::on_resized`] for details.
+    pub async fn on_resized_with(
+        &self,
+        config: StreamConfig,
+    ) -> crate::Result<impl Stream<Item = PhysicalSize>> {
+        let events = self
+            .listen_with::<SizePayload>("tauri://resize", config)
+            .await?;
+
+        Ok(events.map(|event| PhysicalSize::new(event.payload.width, event.payload.height)))
+    }
+
+    /// Listen to window move.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    pub async fn on_moved(&self) -> crate::Result<impl Stream<Item = PhysicalPosition>> {
+        self.on_moved_with(StreamConfig::default()).await
+    }
+
+    /// Listen to window move, buffering events as configured.
+    ///
+    /// See [`WebviewWindow::on_moved`] for details.
+    pub async fn on_moved_with(
+        &self,
+        config: StreamConfig,
+    ) -> crate::Result<impl Stream<Item = PhysicalPosition>> {
+        let events = self
+            .listen_with::<PositionPayload>("tauri://move", config)
+            .await?;
+
+        Ok(events.map(|event| PhysicalPosition::new(event.payload.x, event.payload.y)))
+    }
+
+    /// Listen to window close requested. Emitted when the user requests to closes the window.
+    ///
+    /// Every yielded [`CloseRequestedEvent`] closes the window when dropped, unless [`CloseRequestedEvent::prevent_default`] was called on it.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tauri_sys::{dialog::MessageDialogBuilder, window::current_window};
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let win = current_window();
+    /// let mut requests = win.on_close_requested().await?;
+    ///
+    /// while let Some(mut event) = requests.next().await {
+    ///     let confirmed = MessageDialogBuilder::new()
+    ///         .set_title("Unsaved changes")
+    ///         .ask("Are you sure you want to close the window?")
+    ///         .await?;
+    ///
+    ///     if !confirmed {
+    ///         event.prevent_default();
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn on_close_requested(
+        &self,
+    ) -> crate::Result<impl Stream<Item = CloseRequestedEvent>> {
+        let events = self.listen::<IgnoredAny>("tauri://close-requested").await?;
+        let window = self.clone();
+
+        Ok(events.map(move |event| CloseRequestedEvent {
+            window: window.clone(),
+            id: event.id,
+            prevented: false,
+        }))
+    }
+
+    /// Listen to window focus change.
+    ///
+    /// Yields `true` when the window gained focus and `false` when it lost focus.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listeners when dropped, so no manual unlisten function needs to be called.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    pub async fn on_focus_changed(&self) -> crate::Result<impl Stream<Item = bool>> {
+        self.listen_many(&[
+            ("tauri://focus", |_| Ok(true)),
+            ("tauri://blur", |_| Ok(false)),
+        ])
+        .await
+    }
+
+    /// Listen to window scale change. Emitted when the window's scale factor has changed.
+    ///
+    /// The following user actions can cause DPI changes:
+    /// - Changing the display's resolution.
+    /// - Changing the display's scale factor (e.g. in Control Panel on Windows).
+    /// - Moving the window to a display with a different scale factor.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    pub async fn on_scale_changed(&self) -> crate::Result<impl Stream<Item = ScaleFactorChanged>> {
+        self.on_scale_changed_with(StreamConfig::default()).await
+    }
+
+    /// Listen to window scale change, buffering events as configured.
+    ///
+    /// See [`WebviewWindow::on_scale_changed`] for details.
+    pub async fn on_scale_changed_with(
+        &self,
+        config: StreamConfig,
+    ) -> crate::Result<impl Stream<Item = ScaleFactorChanged>> {
+        let events = self
+            .listen_with::<ScaleFactorChangedPayload>("tauri://scale-change", config)
+            .await?;
+
+        Ok(events.map(|event| ScaleFactorChanged {
+            scale_factor: event.payload.scale_factor,
+            size: PhysicalSize::new(event.payload.size.width, event.payload.size.height),
+        }))
+    }
+
+    /// Listen to a file drop event.
+    ///
+    /// The listener is triggered when the user hovers the selected files on the window, drops the files or cancels the operation.
+    /// Requires [`WebviewWindowBuilder::set_file_drop_enabled`] to be enabled, which is the default.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listeners when dropped, so no manual unlisten function needs to be called.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tauri_sys::window::{current_window, FileDropEvent};
+    ///
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let win = current_window();
+    /// let mut events = win.on_file_drop().await?;
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         FileDropEvent::Hovered(paths) => log::debug!("User hovering {:?}", paths),
+    ///         FileDropEvent::Dropped(paths) => log::debug!("User dropped {:?}", paths),
+    ///         FileDropEvent::Cancelled => log::debug!("File drop cancelled"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn on_file_drop(&self) -> crate::Result<impl Stream<Item = FileDropEvent>> {
+        self.listen_many(&[
+            ("tauri://file-drop", |raw| {
+                Ok(FileDropEvent::Dropped(deserialize_event(raw)?.payload))
+            }),
+            ("tauri://file-drop-hover", |raw| {
+                Ok(FileDropEvent::Hovered(deserialize_event(raw)?.payload))
+            }),
+            ("tauri://file-drop-cancelled", |_| {
+                Ok(FileDropEvent::Cancelled)
+            }),
+        ])
+        .await
+    }
+
+    /// Listen to the window menu item click. The payload is the id of the clicked menu item.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    pub async fn on_menu_clicked(&self) -> crate::Result<impl Stream<Item = String>> {
+        let events = self.listen::<String>("tauri://menu").await?;
+
+        Ok(events.map(|event| event.payload))
+    }
+
+    /// Listen to the system theme change.
+    ///
+    /// The returned Future will automatically clean up it's underlying event listener when dropped, so no manual unlisten function needs to be called.
+    /// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+    pub async fn on_theme_changed(&self) -> crate::Result<impl Stream<Item = Theme>> {
+        let events = self.listen::<Theme>("tauri://theme-changed").await?;
+
+        Ok(events.map(|event| event.payload))
+    }
 }
 
 /// A position represented in logical pixels.
@@ -1031,8 +1449,17 @@ impl Monitor {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// # Panics
+///
+/// Panics if Tauri didn't inject the window metadata, e.g. when running outside of a Tauri window without `mocks::mock_windows`.
 pub fn current_window() -> WebviewWindow {
-    WebviewWindow(inner::getCurrent())
+    let label = current_window_label().expect("window.__TAURI_METADATA__ is missing");
+
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &JsValue::from_str("skip"), &JsValue::TRUE).unwrap();
+
+    WebviewWindow(inner::WebviewWindow::new(&label, options.into()))
 }
 
 /// Gets a list of instances of [`WebviewWindow`] for all available webview windows.
@@ -1058,6 +1485,50 @@ pub fn all_windows() -> impl IntoIterator<Item = WebviewWindow> {
     ArrayIterator::new(raw).map(|r| WebviewWindow(inner::WebviewWindow::from(r)))
 }
 
+/// Listen to menu item clicks of all windows available at the time of the call.
+///
+/// Each event is tagged with the label of the window the clicked menu belongs to.
+///
+/// The returned Future will automatically clean up it's underlying event listeners when dropped, so no manual unlisten function needs to be called.
+/// See [Differences to the JavaScript API](../index.html#differences-to-the-javascript-api) for details.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tauri_sys::window::menu_events;
+///
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut events = menu_events().await?;
+///
+/// while let Some(event) = events.next().await {
+///     match event.menu_item_id.as_str() {
+///         "quit" => log::info!("Quit requested from {}", event.window_label),
+///         id => log::debug!("Unhandled menu item {}", id),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn menu_events() -> crate::Result<impl Stream<Item = MenuEvent>> {
+    let listeners = all_windows().into_iter().map(|win| async move {
+        let events = skip_invalid(
+            win.listen_owned::<String>("tauri://menu", StreamConfig::default())
+                .await?,
+        );
+        let window_label = win.label();
+
+        crate::Result::Ok(events.map(move |event| MenuEvent {
+            window_label: window_label.clone(),
+            menu_item_id: event.payload,
+        }))
+    });
+
+    let streams = future::try_join_all(listeners).await?;
+
+    Ok(stream::select_all(streams))
+}
+
 /// Returns the monitor on which the window currently resides.
 ///
 /// Returns `None` if current monitor can't be detected.
@@ -1132,6 +1603,35 @@ pub async fn available_monitors() -> crate::Result<impl Iterator<Item = Monitor>
     Ok(monitors)
 }
 
+/// An unlisten function and the handler it detaches.
+type Listener = (js_sys::Function, Option<Closure<dyn FnMut(JsValue)>>);
+
+/// Several listeners feeding one channel, so their events are yielded in the order they arrived.
+struct ListenMany<T> {
+    rx: channel::Receiver<T>,
+    listeners: Vec<Listener>,
+}
+
+impl<T> Drop for ListenMany<T> {
+    fn drop(&mut self) {
+        log::debug!("Calling unlisten for listen callbacks");
+        for (unlisten_fn, handler) in &mut self.listeners {
+            event::unlisten(unlisten_fn, handler.take());
+        }
+    }
+}
+
+impl<T> Stream for ListenMany<T> {
+    type Item = T;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
 mod inner {
     use js_sys::Array;
     use wasm_bindgen::{
@@ -1381,7 +1881,6 @@ mod inner {
 
     #[wasm_bindgen(module = "/src/window.js")]
     extern "C" {
-        pub fn getCurrent() -> WebviewWindow;
         pub fn getAll() -> Array;
         #[wasm_bindgen(catch)]
         pub async fn currentMonitor() -> Result<JsValue, JsValue>;
@@ -1,109 +1,1299 @@
+use serde::de::IgnoredAny;
 use serde::Deserialize;
 use serde::Serialize;
-use tauri_sys::{mocks::mock_ipc, tauri};
-use wasm_bindgen::JsError;
+use tauri_sys::{mocks::MockRouter, tauri};
+use wasm_bindgen::JsValue;
 use wasm_bindgen_test::wasm_bindgen_test;
 use wasm_bindgen_test::wasm_bindgen_test_configure;
 
-macro_rules! bail {
-    ($e:expr) => {
-        return Err(JsError::new($e));
-    };
-    ($fmt:expr, $($arg:tt)*) => {
-        return Err(JsError::new(&format!($fmt, $($arg)*)));
+fn main() {
+    wasm_bindgen_test_configure!(run_in_browser);
+}
+
+async fn next_tick() {
+    use wasm_bindgen::JsCast;
+
+    let set_timeout: js_sys::Function =
+        js_sys::Reflect::get(&js_sys::global(), &"setTimeout".into())
+            .unwrap()
+            .unchecked_into();
+    let tick = js_sys::Promise::new(&mut |resolve, _| {
+        set_timeout
+            .call2(&JsValue::NULL, &resolve, &JsValue::from(0))
+            .unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(tick).await.unwrap();
+}
+
+/**
+ * App module
+ */
+
+#[wasm_bindgen_test]
+async fn test_get_version() {
+    use tauri_sys::app::get_version;
+
+    let mut router = MockRouter::new();
+    router.builtin("App", "getAppVersion", |_: IgnoredAny| Ok("1.0.0"));
+    router.install();
+
+    let version = get_version().await.unwrap();
+
+    assert_eq!(version.major, 1);
+    assert_eq!(version.minor, 0);
+    assert_eq!(version.patch, 0)
+}
+
+/**
+ * Cli module
+ */
+
+#[wasm_bindgen_test]
+async fn test_cli_matches() -> Result<(), Box<dyn std::error::Error>> {
+    use std::collections::HashMap;
+    use tauri_sys::cli::{get_matches, ArgValue};
+
+    #[derive(Deserialize)]
+    struct Args {
+        verbose: bool,
+        config: Option<String>,
+        files: Vec<String>,
+    }
+
+    #[derive(Serialize)]
+    struct RawArg {
+        value: ArgValue,
+        occurrences: u32,
+    }
+
+    #[derive(Serialize)]
+    struct RawMatches {
+        args: HashMap<&'static str, RawArg>,
+        subcommand: Option<()>,
+    }
+
+    let mut router = MockRouter::new();
+    router.builtin("Cli", "cliMatches", |_: IgnoredAny| {
+        Ok(RawMatches {
+            args: HashMap::from([
+                (
+                    "verbose",
+                    RawArg {
+                        value: ArgValue::Bool(true),
+                        occurrences: 1,
+                    },
+                ),
+                (
+                    "config",
+                    RawArg {
+                        value: ArgValue::Null,
+                        occurrences: 0,
+                    },
+                ),
+                (
+                    "files",
+                    RawArg {
+                        value: ArgValue::Array(vec!["a.txt".into(), "b.txt".into()]),
+                        occurrences: 2,
+                    },
+                ),
+            ]),
+            subcommand: None,
+        })
+    });
+    router.install();
+
+    let matches = get_matches().await?;
+
+    assert_eq!(matches.args["verbose"].value, ArgValue::Bool(true));
+    assert_eq!(matches.args["config"].value, ArgValue::Null);
+    assert_eq!(matches.args["files"].occurrences, 2);
+    assert!(matches.subcommand.is_none());
+
+    let args: Args = matches.parse_into()?;
+
+    assert!(args.verbose);
+    assert_eq!(args.config, None);
+    assert_eq!(args.files, ["a.txt", "b.txt"]);
+
+    Ok(())
+}
+
+/**
+ * Event module
+ */
+
+#[wasm_bindgen_test]
+async fn test_event_hub() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use std::{cell::RefCell, rc::Rc};
+    use tauri_sys::event::Hub;
+    use wasm_bindgen::JsCast;
+
+    let calls = Rc::new(RefCell::new(Vec::<String>::new()));
+    let handlers = Rc::new(RefCell::new(Vec::<f64>::new()));
+
+    #[derive(Deserialize)]
+    struct ListenMessage {
+        handler: f64,
+    }
+
+    let mut router = MockRouter::new();
+    router
+        .builtin("Event", "listen", {
+            let calls = calls.clone();
+            let handlers = handlers.clone();
+
+            move |message: ListenMessage| {
+                handlers.borrow_mut().push(message.handler);
+                calls.borrow_mut().push("listen".to_string());
+
+                Ok(1)
+            }
+        })
+        .builtin("Event", "unlisten", {
+            let calls = calls.clone();
+
+            move |_: IgnoredAny| {
+                calls.borrow_mut().push("unlisten".to_string());
+
+                Ok(())
+            }
+        });
+    router.install();
+
+    let hub = Hub::new();
+    let mut a = hub.subscribe::<String>("status").await?;
+    let mut b = hub.subscribe::<String>("status").await?;
+
+    assert_eq!(*calls.borrow(), ["listen"]);
+
+    let handler = js_sys::Reflect::get(
+        &js_sys::global(),
+        &format!("_{}", handlers.borrow()[0]).into(),
+    )
+    .unwrap();
+    let event =
+        js_sys::JSON::parse(r#"{"event":"status","id":1,"payload":"ready","windowLabel":null}"#)
+            .unwrap();
+    handler
+        .unchecked_into::<js_sys::Function>()
+        .call1(&JsValue::NULL, &event)
+        .unwrap();
+
+    assert_eq!(a.next().await.unwrap().payload, "ready");
+    assert_eq!(b.next().await.unwrap().payload, "ready");
+
+    drop(a);
+    assert_eq!(*calls.borrow(), ["listen"]);
+
+    drop(b);
+    assert_eq!(*calls.borrow(), ["listen", "unlisten"]);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_listen_drops_handler() -> Result<(), Box<dyn std::error::Error>> {
+    use std::{cell::RefCell, rc::Rc};
+    use tauri_sys::event::listen;
+    use wasm_bindgen::JsCast;
+
+    let handlers = Rc::new(RefCell::new(Vec::<f64>::new()));
+
+    #[derive(Deserialize)]
+    struct ListenMessage {
+        handler: f64,
+    }
+
+    let mut router = MockRouter::new();
+    router
+        .builtin("Event", "listen", {
+            let handlers = handlers.clone();
+
+            move |message: ListenMessage| {
+                handlers.borrow_mut().push(message.handler);
+
+                Ok(1)
+            }
+        })
+        .builtin("Event", "unlisten", |_: IgnoredAny| Ok(()));
+    router.install();
+
+    for _ in 0..10 {
+        let events = listen::<()>("ping").await?;
+        drop(events);
+    }
+
+    // let the pending unlisten calls resolve
+    next_tick().await;
+
+    let event = js_sys::JSON::parse(r#"{"event":"ping","id":1,"payload":null,"windowLabel":null}"#)
+        .unwrap();
+
+    assert_eq!(handlers.borrow().len(), 10);
+    for id in handlers.borrow().iter() {
+        let handler: js_sys::Function =
+            js_sys::Reflect::get(&js_sys::global(), &format!("_{}", id).into())
+                .unwrap()
+                .unchecked_into();
+
+        // invoking a handler whose closure was dropped throws
+        assert!(handler.call1(&JsValue::NULL, &event).is_err());
+    }
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_typed_events() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::{
+        event::{emit_typed, listen_typed, once_typed, TauriEvent},
+        mocks::{clear_mocks, emit_mock_event},
+        tauri_event, Error,
     };
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Progress {
+        done: u32,
+        total: u32,
+    }
+
+    tauri_event! {
+        struct DownloadProgress("download-progress"): Progress;
+    }
+
+    MockRouter::new().install();
+
+    let mut events = listen_typed::<DownloadProgress>().await?;
+
+    emit_typed::<DownloadProgress>(&Progress { done: 1, total: 4 }).await?;
+    assert_eq!(
+        events.next().await.unwrap().payload,
+        Progress { done: 1, total: 4 }
+    );
+
+    // mismatched payloads are skipped by the stream
+    emit_mock_event(DownloadProgress::NAME, &"halfway", None)?;
+    emit_mock_event(
+        DownloadProgress::NAME,
+        &Progress { done: 2, total: 4 },
+        None,
+    )?;
+    assert_eq!(
+        events.next().await.unwrap().payload,
+        Progress { done: 2, total: 4 }
+    );
+
+    // but reported by `once_typed`
+    let (res, emitted) = futures::join!(once_typed::<DownloadProgress>(), async {
+        next_tick().await;
+        emit_mock_event(DownloadProgress::NAME, &"halfway", None)
+    });
+    emitted?;
+    assert!(matches!(res, Err(Error::InvalidPayload { .. })));
+
+    clear_mocks();
+
+    Ok(())
 }
 
-macro_rules! ensure {
-    ($cond:expr) => {
-        if !($cond) {
-            bail!("{}", _failure__stringify!($cond));
-        }
+#[wasm_bindgen_test]
+async fn test_stream_overflow() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::{FutureExt, StreamExt};
+    use tauri_sys::{
+        event::{listen_with, Overflow, StreamConfig},
+        mocks::{clear_mocks, emit_mock_event},
     };
-    ($cond:expr, $e:expr) => {
-        if !($cond) {
-            bail!($e);
+
+    MockRouter::new().install();
+
+    let policies = [
+        (StreamConfig::unbounded(), vec![1, 2, 3, 4]),
+        (StreamConfig::bounded(2, Overflow::DropOldest), vec![3, 4]),
+        (StreamConfig::bounded(2, Overflow::DropNewest), vec![1, 2]),
+        (StreamConfig::bounded(2, Overflow::Coalesce), vec![1, 4]),
+        (StreamConfig::latest(), vec![4]),
+    ];
+
+    for (config, expected) in policies {
+        let mut events = listen_with::<u32>("progress", config).await?;
+
+        // nothing is read until all events were buffered
+        for done in 1..=4 {
+            emit_mock_event("progress", &done, None)?;
         }
-    };
-    ($cond:expr, $fmt:expr, $($arg:tt)*) => {
-        if !($cond) {
-            bail!($fmt, $($arg)*);
+
+        let mut received = Vec::new();
+        while let Some(Some(event)) = events.next().now_or_never() {
+            received.push(event.payload);
         }
+        assert_eq!(received, expected, "{:?}", config);
+    }
+
+    clear_mocks();
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_emit_to() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::{FutureExt, StreamExt};
+    use tauri_sys::{
+        event::{emit_filtered, emit_to},
+        mocks::{clear_mocks, FakeWindowManager},
+        window::WebviewWindow,
+        Error,
+    };
+
+    let windows = FakeWindowManager::new("main", &["editor-1", "editor-2"]);
+
+    let mut router = MockRouter::new();
+    windows.attach(&mut router);
+    router.install();
+
+    let editor_1 = WebviewWindow::get_by_label("editor-1").unwrap();
+    let editor_2 = WebviewWindow::get_by_label("editor-2").unwrap();
+    let mut saved_1 = editor_1.listen::<String>("document-saved").await?;
+    let mut saved_2 = editor_2.listen::<String>("document-saved").await?;
+
+    let results = emit_to(["editor-1", "missing"], "document-saved", &"notes.md").await;
+    assert_eq!(
+        results,
+        [
+            ("editor-1".to_string(), Ok(())),
+            (
+                "missing".to_string(),
+                Err(Error::UnknownWindow("missing".to_string()))
+            ),
+        ]
+    );
+    assert_eq!(saved_1.next().await.unwrap().payload, "notes.md");
+
+    let results = emit_filtered(
+        |label| label.starts_with("editor-"),
+        "document-saved",
+        &"todo.md",
+    )
+    .await;
+    assert_eq!(
+        results,
+        [
+            ("editor-1".to_string(), Ok(())),
+            ("editor-2".to_string(), Ok(())),
+        ]
+    );
+    assert_eq!(saved_1.next().await.unwrap().payload, "todo.md");
+    assert_eq!(saved_2.next().await.unwrap().payload, "todo.md");
+
+    // `editor-2` wasn't targeted by `emit_to`
+    assert!(saved_2.next().now_or_never().is_none());
+
+    clear_mocks();
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_rpc_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::{
+        event::rpc::{request, serve},
+        mocks::{clear_mocks, listener_count, mock_windows},
+        Error,
     };
+
+    mock_windows("main", &[]);
+    MockRouter::new().install();
+
+    let server = serve::<(u32, u32), u32>("add", |(a, b)| async move {
+        a.checked_add(b).ok_or("overflow")
+    })
+    .await?;
+
+    let sum: u32 = request("main", "add", &(1, 2)).await?;
+    assert_eq!(sum, 3);
+
+    let res = request::<_, u32>("main", "add", &(u32::MAX, 1)).await;
+    assert_eq!(res, Err(Error::Rpc("overflow".to_string())));
+
+    drop(server);
+    next_tick().await;
+    assert_eq!(listener_count("rpc:add:request"), 0);
+    assert_eq!(listener_count("rpc:add:response"), 0);
+
+    clear_mocks();
+
+    Ok(())
 }
 
-macro_rules! _failure__stringify {
-    ($($inner:tt)*) => {
-        stringify! { $($inner)* }
+#[wasm_bindgen_test]
+async fn test_rpc_timeout() -> Result<(), Box<dyn std::error::Error>> {
+    use std::time::Duration;
+    use tauri_sys::{
+        event::rpc::{request_with_timeout, serve},
+        mocks::{clear_mocks, mock_windows},
+        Error,
+    };
+
+    mock_windows("main", &["settings"]);
+    MockRouter::new().install();
+
+    // requests addressed to other windows are ignored
+    let _server = serve::<(), ()>("ping", |()| async { Ok::<_, String>(()) }).await?;
+
+    let timeout = Duration::from_millis(50);
+    let res = request_with_timeout::<_, ()>("settings", "ping", &(), timeout).await;
+    assert_eq!(res, Err(Error::Timeout(timeout)));
+
+    clear_mocks();
+
+    Ok(())
+}
+
+/**
+ * Tauri module
+ */
+
+#[wasm_bindgen_test]
+async fn test_invoke() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Serialize, Deserialize)]
+    struct AddPayload {
+        a: u32,
+        b: u32,
     }
+
+    let mut router = MockRouter::new();
+    router.command::<AddPayload, u32>("add", |args| Ok(args.a + args.b));
+    router.install();
+
+    let out = tauri::invoke::<_, u32>("add", &AddPayload { a: 12, b: 15 }).await?;
+
+    assert_eq!(out, 27);
+
+    Ok(())
 }
 
-#[derive(Deserialize)]
-struct ApiRequestInner {
-    cmd: String,
+#[wasm_bindgen_test]
+async fn test_invoke_with_error() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::{tauri::InvokeError, Error};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum LoginError {
+        WrongPassword { attempts: u32 },
+    }
+
+    let mut router = MockRouter::new();
+    router
+        .command("login", |_: IgnoredAny| {
+            Err::<(), _>(
+                serde_wasm_bindgen::to_value(&LoginError::WrongPassword { attempts: 3 }).unwrap(),
+            )
+        })
+        .command("readFile", |_: IgnoredAny| {
+            Err::<(), _>(JsValue::from_str("'fs > readFile' not in the allowlist"))
+        });
+    router.install();
+
+    let res = tauri::invoke_with_error::<_, (), LoginError>("login", &()).await;
+    assert_eq!(
+        res,
+        Err(InvokeError::Command(LoginError::WrongPassword {
+            attempts: 3
+        }))
+    );
+
+    let res = tauri::invoke_with_error::<_, (), String>("readFile", &()).await;
+    assert!(matches!(
+        res,
+        Err(InvokeError::Invoke(Error::Permission(_)))
+    ));
+
+    Ok(())
 }
 
-#[derive(Deserialize)]
-struct ApiRequest {
-    #[serde(rename = "__tauriModule")]
-    __tauri_module: String,
-    message: ApiRequestInner,
+#[wasm_bindgen_test]
+async fn test_call_command() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::tauri::{call, InvokeError};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum ConfigError {
+        NotFound { file_name: String },
+    }
+
+    tauri_sys::command! {
+        struct ReadConfig("read_config") -> Result<String, ConfigError> {
+            file_name: String,
+            #[serde(rename = "create")]
+            create_missing: bool,
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ReadConfigArgs {
+        file_name: String,
+        create: bool,
+    }
+
+    let mut router = MockRouter::new();
+    router.command("read_config", |args: ReadConfigArgs| {
+        if args.file_name == "app.toml" || args.create {
+            Ok("debug = true")
+        } else {
+            Err(serde_wasm_bindgen::to_value(&ConfigError::NotFound {
+                file_name: args.file_name,
+            })
+            .unwrap())
+        }
+    });
+    router.install();
+
+    let res = call::<ReadConfig>(&ReadConfig {
+        file_name: "app.toml".to_string(),
+        create_missing: false,
+    })
+    .await;
+    assert_eq!(res, Ok("debug = true".to_string()));
+
+    let res = call::<ReadConfig>(&ReadConfig {
+        file_name: "missing.toml".to_string(),
+        create_missing: false,
+    })
+    .await;
+    assert_eq!(
+        res,
+        Err(InvokeError::Command(ConfigError::NotFound {
+            file_name: "missing.toml".to_string()
+        }))
+    );
+
+    Ok(())
 }
 
-fn main() {
-    wasm_bindgen_test_configure!(run_in_browser);
+/**
+ * Http module
+ */
+
+#[wasm_bindgen_test]
+async fn test_http_request() -> Result<(), Box<dyn std::error::Error>> {
+    use std::collections::HashMap;
+    use tauri_sys::http::{Client, ResponseType};
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct RawResponse {
+        url: &'static str,
+        status: u16,
+        headers: HashMap<&'static str, &'static str>,
+        raw_headers: HashMap<&'static str, Vec<&'static str>>,
+        data: &'static str,
+    }
+
+    let mut router = MockRouter::new();
+    router
+        .builtin("Http", "createClient", |_: IgnoredAny| Ok(1))
+        .builtin("Http", "httpRequest", |_: IgnoredAny| {
+            Ok(RawResponse {
+                url: "https://tauri.app",
+                status: 200,
+                headers: HashMap::from([("content-type", "text/plain")]),
+                raw_headers: HashMap::from([("content-type", vec!["text/plain"])]),
+                data: "Hello Tauri",
+            })
+        })
+        .builtin("Http", "dropClient", |_: IgnoredAny| Ok(()));
+    router.install();
+
+    let client = Client::new().await?;
+    let res = client
+        .get("https://tauri.app")
+        .set_response_type(ResponseType::Text)
+        .send::<String>()
+        .await?;
+
+    assert!(res.ok);
+    assert_eq!(res.status, 200);
+    assert_eq!(res.data, "Hello Tauri");
+    assert_eq!(res.headers["content-type"], "text/plain");
+
+    Ok(())
 }
 
 /**
- * App module
+ * Shell module
  */
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecuteMessage {
+    program: String,
+    args: Vec<String>,
+    on_event_fn: f64,
+}
+
+/// Calls the `onEvent` callback the JS `execute` function registered for a command.
+fn send_command_event(handler: f64, event: &str, payload: impl Serialize) {
+    use wasm_bindgen::JsCast;
+
+    #[derive(Serialize)]
+    struct CommandEvent<'a, T> {
+        event: &'a str,
+        payload: T,
+    }
+
+    let event = CommandEvent { event, payload }
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap();
+
+    js_sys::Reflect::get(&js_sys::global(), &format!("_{}", handler).into())
+        .unwrap()
+        .unchecked_into::<js_sys::Function>()
+        .call1(&JsValue::NULL, &event)
+        .unwrap();
+}
+
+#[derive(Serialize)]
+struct TerminatedPayload {
+    code: Option<i32>,
+    signal: Option<i32>,
+}
+
 #[wasm_bindgen_test]
-async fn test_get_version() {
-    use tauri_sys::app::get_version;
+fn test_sidecar_names() {
+    use tauri_sys::shell::Command;
+
+    for name in [
+        "my-sidecar",
+        "my_sidecar.exe",
+        "..sidecar",
+        "binaries/my-sidecar",
+        "binaries/x86/my-sidecar",
+    ] {
+        assert!(Command::sidecar(name).is_ok(), "`{}` was rejected", name);
+    }
+
+    for name in [
+        "",
+        ".",
+        "..",
+        "/usr/bin/my-sidecar",
+        "../my-sidecar",
+        "binaries/../my-sidecar",
+        "binaries/./my-sidecar",
+        "binaries//my-sidecar",
+        "binaries/",
+        "binaries\\my-sidecar",
+        "..\\my-sidecar",
+        "C:my-sidecar",
+    ] {
+        assert!(Command::sidecar(name).is_err(), "`{}` was accepted", name);
+    }
+}
 
-    mock_ipc(|cmd, payload| {
-        ensure!(cmd.as_str() == "tauri", "unknown command");
+#[wasm_bindgen_test]
+async fn test_command_output() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::shell::{Command, Output};
 
-        let payload: ApiRequest = serde_wasm_bindgen::from_value(payload).unwrap();
+    let mut router = MockRouter::new();
+    router.builtin("Shell", "execute", |message: ExecuteMessage| {
+        assert_eq!(message.program, "echo");
+        assert_eq!(message.args, ["first", "second"]);
 
-        ensure!(payload.__tauri_module == "App");
-        ensure!(payload.message.cmd == "getAppVersion");
+        send_command_event(message.on_event_fn, "Stdout", "first");
+        send_command_event(message.on_event_fn, "Stderr", "warning");
+        send_command_event(message.on_event_fn, "Stdout", "second");
+        send_command_event(
+            message.on_event_fn,
+            "Terminated",
+            TerminatedPayload {
+                code: Some(0),
+                signal: None,
+            },
+        );
 
-        Ok("1.0.0")
+        Ok(42)
     });
+    router.install();
 
-    let version = get_version().await.unwrap();
+    let output = Command::new("echo")
+        .add_args(["first", "second"])
+        .output()
+        .await?;
 
-    assert_eq!(version.major, 1);
-    assert_eq!(version.minor, 0);
-    assert_eq!(version.patch, 0)
+    assert_eq!(
+        output,
+        Output {
+            code: Some(0),
+            signal: None,
+            stdout: "first\nsecond".to_string(),
+            stderr: "warning".to_string(),
+        }
+    );
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_command_spawn() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+    use tauri_sys::shell::{Command, CommandEvent};
+
+    #[derive(Deserialize)]
+    struct StdinWriteMessage {
+        pid: u32,
+        buffer: String,
+    }
+
+    let on_event_fn = Rc::new(Cell::new(None));
+    let written = Rc::new(RefCell::new(Vec::<String>::new()));
+
+    let mut router = MockRouter::new();
+    router
+        .builtin("Shell", "execute", {
+            let on_event_fn = on_event_fn.clone();
+
+            move |message: ExecuteMessage| {
+                on_event_fn.set(Some(message.on_event_fn));
+                send_command_event(message.on_event_fn, "Stdout", "ready");
+
+                Ok(42)
+            }
+        })
+        .builtin("Shell", "stdinWrite", {
+            let written = written.clone();
+
+            move |message: StdinWriteMessage| {
+                assert_eq!(message.pid, 42);
+                written.borrow_mut().push(message.buffer);
+
+                Ok(())
+            }
+        })
+        .builtin("Shell", "killChild", move |_: IgnoredAny| {
+            let terminated = TerminatedPayload {
+                code: None,
+                signal: Some(9),
+            };
+            send_command_event(on_event_fn.get().unwrap(), "Terminated", terminated);
+
+            Ok(())
+        });
+    router.install();
+
+    let (mut events, child) = Command::new("node").spawn().await?;
+    assert_eq!(child.pid(), 42);
+    assert_eq!(
+        events.next().await,
+        Some(CommandEvent::Stdout("ready".to_string()))
+    );
+
+    child.write_str("console.log('Hello Tauri')\n").await?;
+    assert_eq!(*written.borrow(), ["console.log('Hello Tauri')\n"]);
+
+    child.kill().await?;
+    assert_eq!(
+        events.next().await,
+        Some(CommandEvent::Terminated {
+            code: None,
+            signal: Some(9)
+        })
+    );
+    // the stream ends once the child terminated
+    assert_eq!(events.next().await, None);
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_open() -> Result<(), Box<dyn std::error::Error>> {
+    use std::{cell::RefCell, rc::Rc};
+    use tauri_sys::shell::{open, OpenWith};
+    use url::Url;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct OpenMessage {
+        path: String,
+        with: Option<String>,
+    }
+
+    let opened = Rc::new(RefCell::new(Vec::new()));
+
+    let mut router = MockRouter::new();
+    router.builtin("Shell", "open", {
+        let opened = opened.clone();
+
+        move |message: OpenMessage| {
+            opened.borrow_mut().push(message);
+
+            Ok(())
+        }
+    });
+    router.install();
+
+    open(Url::parse("https://tauri.app")?, None).await?;
+    open(Url::parse("https://tauri.app")?, Some(OpenWith::Firefox)).await?;
+
+    assert_eq!(
+        *opened.borrow(),
+        [
+            OpenMessage {
+                path: "https://tauri.app/".to_string(),
+                with: None,
+            },
+            OpenMessage {
+                path: "https://tauri.app/".to_string(),
+                with: Some("firefox".to_string()),
+            },
+        ]
+    );
+
+    Ok(())
 }
 
 /**
- * Tauri module
+ * Plugin log module
  */
 
 #[wasm_bindgen_test]
-async fn test_invoke() -> Result<(), Box<dyn std::error::Error>> {
-    #[derive(Serialize, Deserialize)]
+async fn test_tauri_logger() -> Result<(), Box<dyn std::error::Error>> {
+    use log::{Level, LevelFilter, Log, Record};
+    use std::{cell::RefCell, rc::Rc};
+    use tauri_sys::{mocks::clear_mocks, plugin_log::TauriLogger};
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct LogMessage {
+        level: u16,
+        message: String,
+        location: String,
+    }
+
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    let logged = messages.clone();
+
+    let mut router = MockRouter::new();
+    router.command("plugin:log|log", move |message: LogMessage| {
+        logged.borrow_mut().push(message);
+        Ok(())
+    });
+    router.install();
+
+    log::set_max_level(LevelFilter::Trace);
+    TauriLogger.log(
+        &Record::builder()
+            .level(Level::Warn)
+            .target("app::sync")
+            .args(format_args!("{} files left", 3))
+            .build(),
+    );
+    // the record is sent once the logger's spawned task ran
+    next_tick().await;
+
+    assert_eq!(
+        *messages.borrow(),
+        [LogMessage {
+            level: 4,
+            message: "3 files left".to_string(),
+            location: "app::sync".to_string(),
+        }]
+    );
+
+    clear_mocks();
+
+    Ok(())
+}
+
+/**
+ * Window module
+ */
+
+#[wasm_bindgen_test]
+async fn test_close_requested() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::{
+        mocks::{clear_mocks, emit_mock_event, FakeWindowManager},
+        window::WebviewWindow,
+    };
+
+    let windows = FakeWindowManager::new("main", &["settings"]);
+
+    let mut router = MockRouter::new();
+    windows.attach(&mut router);
+    router.install();
+
+    let settings = WebviewWindow::get_by_label("settings").unwrap();
+    let mut requests = settings.on_close_requested().await?;
+
+    // a prevented request keeps the window open
+    emit_mock_event("tauri://close-requested", &(), Some("settings"))?;
+    let mut event = requests.next().await.unwrap();
+    assert_eq!(event.window().label(), "settings");
+    event.prevent_default();
+    drop(event);
+    next_tick().await;
+    assert!(windows.state("settings").is_some());
+
+    // dropping the request closes the window
+    emit_mock_event("tauri://close-requested", &(), Some("settings"))?;
+    let event = requests.next().await.unwrap();
+    assert!(!event.is_default_prevented());
+    drop(event);
+    next_tick().await;
+    assert!(windows.state("settings").is_none());
+
+    clear_mocks();
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_resized_latest() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::{FutureExt, StreamExt};
+    use tauri_sys::{
+        event::StreamConfig,
+        mocks::{clear_mocks, FakeWindowManager},
+        window::{current_window, PhysicalSize},
+    };
+
+    let windows = FakeWindowManager::new("main", &[]);
+
+    let mut router = MockRouter::new();
+    windows.attach(&mut router);
+    router.install();
+
+    let main = current_window();
+    let mut sizes = main.on_resized_with(StreamConfig::latest()).await?;
+
+    for width in [800, 1024, 1280] {
+        main.set_size(PhysicalSize::new(width, 720)).await?;
+    }
+    next_tick().await;
+
+    // only the most recent size is kept
+    let size = sizes.next().await.unwrap();
+    assert_eq!((size.width(), size.height()), (1280, 720));
+    assert!(sizes.next().now_or_never().is_none());
+
+    clear_mocks();
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_focus_changed() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::{
+        mocks::{clear_mocks, emit_mock_event, FakeWindowManager},
+        window::current_window,
+    };
+
+    let windows = FakeWindowManager::new("main", &[]);
+
+    let mut router = MockRouter::new();
+    windows.attach(&mut router);
+    router.install();
+
+    let focus = current_window().on_focus_changed().await?;
+
+    for event in ["tauri://blur", "tauri://focus", "tauri://blur"] {
+        emit_mock_event(event, &(), Some("main"))?;
+    }
+
+    // events are yielded in the order they were emitted
+    let changes: Vec<bool> = focus.take(3).collect().await;
+    assert_eq!(changes, [false, true, false]);
+
+    clear_mocks();
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_file_drop() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use std::path::PathBuf;
+    use tauri_sys::{
+        mocks::{clear_mocks, emit_mock_event, FakeWindowManager},
+        window::{current_window, FileDropEvent},
+    };
+
+    let windows = FakeWindowManager::new("main", &[]);
+
+    let mut router = MockRouter::new();
+    windows.attach(&mut router);
+    router.install();
+
+    let events = current_window().on_file_drop().await?;
+
+    let paths = ["/home/user/notes.txt"];
+    emit_mock_event("tauri://file-drop-hover", &paths, Some("main"))?;
+    emit_mock_event("tauri://file-drop-cancelled", &(), Some("main"))?;
+    emit_mock_event("tauri://file-drop-hover", &paths, Some("main"))?;
+    emit_mock_event("tauri://file-drop", &paths, Some("main"))?;
+
+    let paths = vec![PathBuf::from(paths[0])];
+    let events: Vec<FileDropEvent> = events.take(4).collect().await;
+    assert_eq!(
+        events,
+        [
+            FileDropEvent::Hovered(paths.clone()),
+            FileDropEvent::Cancelled,
+            FileDropEvent::Hovered(paths.clone()),
+            FileDropEvent::Dropped(paths),
+        ]
+    );
+
+    clear_mocks();
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_menu_events() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::{
+        mocks::{clear_mocks, emit_mock_event, FakeWindowManager},
+        window::{current_window, menu_events, MenuEvent},
+    };
+
+    let windows = FakeWindowManager::new("main", &["settings"]);
+
+    let mut router = MockRouter::new();
+    windows.attach(&mut router);
+    router.install();
+
+    let mut clicks = current_window().on_menu_clicked().await?;
+    let mut events = menu_events().await?;
+
+    emit_mock_event("tauri://menu", &"quit", Some("main"))?;
+    assert_eq!(clicks.next().await.unwrap(), "quit");
+    assert_eq!(
+        events.next().await.unwrap(),
+        MenuEvent {
+            window_label: "main".to_string(),
+            menu_item_id: "quit".to_string(),
+        }
+    );
+
+    emit_mock_event("tauri://menu", &"preferences", Some("settings"))?;
+    assert_eq!(
+        events.next().await.unwrap(),
+        MenuEvent {
+            window_label: "settings".to_string(),
+            menu_item_id: "preferences".to_string(),
+        }
+    );
+
+    clear_mocks();
+
+    Ok(())
+}
+
+/**
+ * Mocks module
+ */
+
+#[wasm_bindgen_test]
+async fn test_ipc_recorder() -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_sys::{app::get_version, mocks::IpcRecorder};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
     struct AddPayload {
         a: u32,
         b: u32,
     }
 
-    mock_ipc(|cmd, payload| match cmd.as_str() {
-        "add" => {
-            let args: AddPayload = serde_wasm_bindgen::from_value(payload).unwrap();
+    let mut router = MockRouter::new();
+    router
+        .command::<AddPayload, u32>("add", |args| Ok(args.a + args.b))
+        .builtin("App", "getAppVersion", |_: IgnoredAny| Ok("1.0.0"));
 
-            Ok(args.a + args.b)
-        }
-        _ => Err(JsError::new("Unknown command")),
-    });
+    let recorder = IpcRecorder::new();
+    recorder.install(router.into_handler());
 
-    let out = tauri::invoke::<_, u32>("add", &AddPayload { a: 12, b: 15 }).await?;
+    tauri::invoke::<_, u32>("add", &AddPayload { a: 1, b: 2 }).await?;
+    tauri::invoke::<_, u32>("add", &AddPayload { a: 3, b: 4 }).await?;
+    get_version().await?;
 
-    assert_eq!(out, 27);
+    recorder.assert_call_count("add", 2);
+    recorder.assert_call_count("App.getAppVersion", 1);
+    recorder.assert_called_with("add", &AddPayload { a: 3, b: 4 });
+
+    let calls = recorder.take_calls();
+    let cmds: Vec<&str> = calls.iter().map(|call| call.cmd.as_str()).collect();
+    assert_eq!(cmds, ["add", "add", "App.getAppVersion"]);
+    assert!(recorder.calls().is_empty());
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_emit_mock_event() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::{
+        event::{emit, listen},
+        mocks::{clear_mocks, emit_mock_event, listener_count},
+    };
+
+    MockRouter::new().install();
+
+    let mut events = listen::<String>("greeting").await?;
+    assert_eq!(listener_count("greeting"), 1);
+
+    emit_mock_event("greeting", &"hello", None)?;
+    assert_eq!(events.next().await.unwrap().payload, "hello");
+
+    // events emitted by the frontend are delivered as well
+    emit("greeting", &"hi").await?;
+    assert_eq!(events.next().await.unwrap().payload, "hi");
+
+    drop(events);
+    next_tick().await;
+    assert_eq!(listener_count("greeting"), 0);
+
+    clear_mocks();
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_fake_window_manager() -> Result<(), Box<dyn std::error::Error>> {
+    use futures::StreamExt;
+    use tauri_sys::{
+        mocks::{clear_mocks, FakeWindowManager},
+        window::{current_window, PhysicalSize, WebviewWindow, WebviewWindowBuilder},
+    };
+
+    let windows = FakeWindowManager::new("main", &["settings"]);
+
+    let mut router = MockRouter::new();
+    windows.attach(&mut router);
+    router.install();
+
+    let main = current_window();
+    let mut resized = main.on_resized().await?;
+
+    main.set_size(PhysicalSize::new(1024, 768)).await?;
+    let size = resized.next().await.unwrap();
+    assert_eq!((size.width(), size.height()), (1024, 768));
+
+    main.set_title("Tauri").await?;
+    main.maximize().await?;
+    assert!(main.is_maximized().await?);
+    let size = resized.next().await.unwrap();
+    assert_eq!((size.width(), size.height()), (1024, 768));
+
+    let settings = WebviewWindow::get_by_label("settings").unwrap();
+    settings.set_focus().await?;
+
+    let main_state = windows.state("main").unwrap();
+    assert_eq!(main_state.title, "Tauri");
+    assert_eq!((main_state.width, main_state.height), (1024, 768));
+    assert!(!main_state.focused);
+    assert!(windows.state("settings").unwrap().focused);
+
+    let about = WebviewWindowBuilder::new("about")
+        .set_title("About")
+        .set_size(PhysicalSize::new(400, 300))
+        .build()
+        .await?;
+    let about_state = windows.state("about").unwrap();
+    assert_eq!(about_state.title, "About");
+    assert_eq!((about_state.width, about_state.height), (400, 300));
+    assert!(about_state.focused);
+    assert!(WebviewWindow::get_by_label("about").is_some());
+
+    about.close().await?;
+    assert!(windows.state("about").is_none());
+    assert!(WebviewWindow::get_by_label("about").is_none());
+
+    clear_mocks();
+
+    Ok(())
+}
+
+#[wasm_bindgen_test]
+async fn test_fake_fs() -> Result<(), Box<dyn std::error::Error>> {
+    use std::path::Path;
+    use tauri_sys::{
+        fs::{self, BaseDirectory},
+        mocks::{clear_mocks, FakeEntry, FakeFs},
+    };
+
+    let files = FakeFs::new();
+    files
+        .file(BaseDirectory::AppData, "notes/todo.txt", "buy milk")
+        .dir(BaseDirectory::AppData, "notes/archive");
+
+    let mut router = MockRouter::new();
+    files.attach(&mut router);
+    router.install();
+
+    let todo = fs::read_text_file(Path::new("notes/todo.txt"), BaseDirectory::AppData).await?;
+    assert_eq!(todo, "buy milk");
+
+    fs::write_text_file(Path::new("notes/done.txt"), "walk", BaseDirectory::AppData).await?;
+    fs::rename_file(
+        Path::new("notes/done.txt"),
+        Path::new("notes/archive/done.txt"),
+        BaseDirectory::AppData,
+    )
+    .await?;
+
+    let entries = fs::read_dir_all(Path::new("notes"), BaseDirectory::AppData).await?;
+    let archive = entries
+        .iter()
+        .find(|entry| entry.name.as_deref() == Some("archive"))
+        .unwrap();
+    assert_eq!(archive.children.as_ref().unwrap().len(), 1);
+
+    assert!(
+        fs::read_text_file(Path::new("missing.txt"), BaseDirectory::AppData)
+            .await
+            .is_err()
+    );
+    assert!(fs::remove_dir(Path::new("notes"), BaseDirectory::AppData)
+        .await
+        .is_err());
+    assert!(!fs::exists(Path::new("notes/done.txt"), BaseDirectory::AppData).await?);
+
+    // existing files are replaced, like `std::fs::rename` does
+    fs::write_text_file(Path::new("notes/done.txt"), "cook", BaseDirectory::AppData).await?;
+    fs::rename_file(
+        Path::new("notes/done.txt"),
+        Path::new("notes/archive/done.txt"),
+        BaseDirectory::AppData,
+    )
+    .await?;
+    // but a file can't replace a directory
+    assert!(fs::rename_file(
+        Path::new("notes/todo.txt"),
+        Path::new("notes/archive"),
+        BaseDirectory::AppData,
+    )
+    .await
+    .is_err());
+    // directories can't be moved into themselves
+    assert!(fs::rename_file(
+        Path::new("notes"),
+        Path::new("notes/archive/notes"),
+        BaseDirectory::AppData,
+    )
+    .await
+    .is_err());
+
+    assert_eq!(
+        files
+            .snapshot(BaseDirectory::AppData)
+            .get(Path::new("notes/archive/done.txt")),
+        Some(&FakeEntry::File(b"cook".to_vec()))
+    );
+
+    clear_mocks();
 
     Ok(())
 }